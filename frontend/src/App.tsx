@@ -1,15 +1,37 @@
-import React, { useState } from 'react';
-import { Layout, Upload, Button, message, Spin } from 'antd';
+import React, { useEffect, useState } from 'react';
+import { Layout, Upload, Button, message, Spin, Select, Space } from 'antd';
 import { UploadOutlined } from '@ant-design/icons';
 import GraphVisualization from './components/GraphVisualization';
 import './App.css';
 
 const { Header, Content } = Layout;
 
+const API_BASE = 'http://localhost:8080';
+
+interface DatasetInfo {
+  name: string;
+  nodes: number;
+  edges: number;
+  density: number;
+  known_optimum: number | null;
+}
+
+// 解析失败的文件只有 name 与 error
+type DatasetEntry = DatasetInfo | { name: string; error: string };
+
 const App: React.FC = () => {
   const [edges, setEdges] = useState<[number, number][]>([]);
   const [maxClique, setMaxClique] = useState<number[]>([]);
   const [loading, setLoading] = useState(false);
+  const [bounds, setBounds] = useState<[number, number] | null>(null);
+  const [datasets, setDatasets] = useState<DatasetEntry[]>([]);
+
+  useEffect(() => {
+    fetch(`${API_BASE}/api/datasets`)
+      .then(response => response.json())
+      .then(setDatasets)
+      .catch(() => message.error('无法获取数据集列表'));
+  }, []);
 
   const handleFileUpload = (file: File) => {
     const reader = new FileReader();
@@ -26,51 +48,125 @@ const App: React.FC = () => {
       });
 
       setEdges(newEdges);
-      findMaxClique(newEdges);
     };
     reader.readAsText(file);
+    findMaxClique(file);
     return false;
   };
 
-  const findMaxClique = async (graphEdges: [number, number][]) => {
-    setLoading(true);
-    try {
-      const response = await fetch('http://localhost:8080/api/find-max-clique', {
-        method: 'POST',
-        headers: {
-          'Content-Type': 'application/json',
-        },
-        body: JSON.stringify({ edges: graphEdges }),
+  // 订阅任务事件：每次进度高亮当前最优团，任务结束时返回结果
+  const watchJob = (id: number) =>
+    new Promise<{ max_clique: number[] }>((resolve, reject) => {
+      const events = new EventSource(`${API_BASE}/api/jobs/${id}/events`);
+      events.addEventListener('progress', (e) => {
+        const progress = JSON.parse((e as MessageEvent).data);
+        setMaxClique(progress.clique);
+        setBounds([progress.size, progress.upper_bound]);
+      });
+      events.addEventListener('state', (e) => {
+        const job = JSON.parse((e as MessageEvent).data);
+        if (job.status === 'done') {
+          events.close();
+          resolve(job.result);
+        } else if (job.status === 'cancelled') {
+          events.close();
+          reject(new Error(`job ${id} cancelled`));
+        } else if (job.status === 'failed') {
+          events.close();
+          reject(new Error(job.error));
+        }
       });
+      events.onerror = () => {
+        events.close();
+        reject(new Error('event stream closed'));
+      };
+    });
 
+  // 提交任务后通过 SSE 接收进度直到任务结束
+  const runJob = async (submit: () => Promise<Response>) => {
+    setLoading(true);
+    setBounds(null);
+    try {
+      const response = await submit();
       if (!response.ok) {
-        throw new Error('Network response was not ok');
+        // 解析错误带有行号与列号
+        const error = await response.json().catch(() => null);
+        throw new Error(error?.error ?? 'Network response was not ok');
       }
 
-      const data = await response.json();
-      setMaxClique(data.max_clique);
+      const job = await response.json();
+      const result = await watchJob(job.id);
+      setMaxClique(result.max_clique);
       message.success('最大团计算完成！');
     } catch (error) {
-      message.error('计算失败，请重试');
+      message.error(`计算失败：${(error as Error).message}`);
     } finally {
       setLoading(false);
     }
   };
 
+  // 原样上传文件由服务端解析
+  const findMaxClique = (file: File) =>
+    runJob(() =>
+      fetch(`${API_BASE}/api/jobs/upload?name=${encodeURIComponent(file.name)}`, {
+        method: 'POST',
+        headers: {
+          'Content-Type': 'application/octet-stream',
+        },
+        body: file,
+      }),
+    );
+
+  // 服务端自带的数据集：取回边用于绘制，求解直接在服务端进行
+  const solveDataset = async (name: string) => {
+    setMaxClique([]);
+    try {
+      const response = await fetch(`${API_BASE}/api/datasets/${encodeURIComponent(name)}`);
+      const graph = await response.json();
+      setEdges(graph.edges);
+    } catch (error) {
+      message.error(`无法获取数据集：${(error as Error).message}`);
+      return;
+    }
+    await runJob(() =>
+      fetch(`${API_BASE}/api/datasets/${encodeURIComponent(name)}/solve`, { method: 'POST' }),
+    );
+  };
+
   return (
     <Layout className="layout">
       <Header style={{ background: '#fff', padding: '0 20px' }}>
         <h1>最大团算法可视化</h1>
-        <Upload
-          beforeUpload={handleFileUpload}
-          showUploadList={false}
-          accept=".col,.clq"
-        >
-          <Button icon={<UploadOutlined />}>上传DIMACS文件(.col/.clq)</Button>
-        </Upload>
+        <Space>
+          <Upload
+            beforeUpload={handleFileUpload}
+            showUploadList={false}
+            accept=".col,.clq"
+          >
+            <Button icon={<UploadOutlined />}>上传DIMACS文件(.col/.clq)</Button>
+          </Upload>
+          <Select
+            placeholder="选择数据集"
+            style={{ width: 360 }}
+            disabled={loading}
+            onChange={solveDataset}
+            options={datasets.map(d => 'error' in d ? {
+              value: d.name,
+              label: `${d.name}（${d.error}）`,
+              disabled: true,
+            } : {
+              value: d.name,
+              label: `${d.name}（${d.nodes} 节点，${d.edges} 边` +
+                (d.known_optimum !== null ? `，最优 ${d.known_optimum}）` : '）'),
+            })}
+          />
+        </Space>
       </Header>
       <Content style={{ padding: '20px' }}>
-        <Spin spinning={loading}>
+        {loading && bounds && (
+          <p>当前最优团规模 {bounds[0]}，上界 {bounds[1]}</p>
+        )}
+        <Spin spinning={loading && !bounds}>
           <GraphVisualization edges={edges} maxClique={maxClique} />
         </Spin>
       </Content>
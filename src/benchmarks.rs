@@ -0,0 +1,47 @@
+// DIMACS 基准图（data 目录中的文件名）已知的最大团规模
+pub const KNOWN_OPTIMA: &[(&str, usize)] = &[
+    ("brock200_2.clq", 12),
+    ("brock200_4.clq", 17),
+    ("brock400_2.clq", 29),
+    ("brock400_4.clq", 33),
+    ("brock800_2.clq", 24),
+    ("brock800_4.clq", 26),
+    ("C125.9.clq", 34),
+    ("C250.9.clq", 44),
+    ("C500.9.clq", 57),
+    ("C1000.9.clq", 68),
+    ("C2000.9.clq", 80),
+    ("DSJC1000_5.clq", 15),
+    ("DSJC500_5.clq", 13),
+    ("C2000.5.clq", 16),
+    ("C4000.5.clq", 18),
+    ("MANN_a27.clq", 126),
+    ("MANN_a45.clq", 345),
+    ("MANN_a81.clq", 1100),
+    ("gen200_p0.9_44.clq", 44),
+    ("gen200_p0.9_55.clq", 55),
+    ("gen400_p0.9_55.clq", 55),
+    ("gen400_p0.9_65.clq", 65),
+    ("gen400_p0.9_75.clq", 75),
+    ("hamming10-4.clq", 40),
+    ("hamming8-4.clq", 16),
+    ("keller4.clq", 11),
+    ("keller5.clq", 27),
+    ("keller6.clq", 59),
+    ("p_hat300-1.clq", 8),
+    ("p_hat300-2.clq", 25),
+    ("p_hat300-3.clq", 36),
+    ("p_hat700-1.clq", 11),
+    ("p_hat700-2.clq", 44),
+    ("p_hat700-3.clq", 62),
+    ("p_hat1500-1.clq", 12),
+    ("p_hat1500-2.clq", 65),
+    ("p_hat1500-3.clq", 94),
+];
+
+pub fn known_optimum(name: &str) -> Option<usize> {
+    KNOWN_OPTIMA
+        .iter()
+        .find(|&&(file, _)| file == name)
+        .map(|&(_, size)| size)
+}
@@ -0,0 +1,108 @@
+use clique::benchmarks::known_optimum;
+use clique::graph_reader::{Format, GraphErr, LabeledGraph, read_graph};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DatasetInfo {
+    pub name: String,
+    pub nodes: usize,
+    pub edges: usize,
+    pub density: f64,
+    pub known_optimum: Option<usize>,
+}
+
+// 列表中的一项：解析失败的文件只带错误信息，不影响其它文件
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum DatasetEntry {
+    Info(DatasetInfo),
+    Error { name: String, error: String },
+}
+
+pub struct Dataset {
+    path: PathBuf,
+    name: String,
+    // 第一次列出时解析整个文件统计规模，之后复用
+    info: OnceLock<DatasetInfo>,
+}
+
+impl Dataset {
+    pub fn load(&self) -> Result<LabeledGraph, GraphErr> {
+        read_graph(self.path.to_str().unwrap())
+    }
+
+    pub fn info(&self) -> Result<DatasetInfo, GraphErr> {
+        if let Some(info) = self.info.get() {
+            return Ok(info.clone());
+        }
+        let graph = self.load()?.graph;
+        let (n, m) = (graph.node_count(), graph.edge_count());
+        let info = DatasetInfo {
+            name: self.name.clone(),
+            nodes: n,
+            edges: m,
+            density: if n > 1 {
+                2.0 * m as f64 / (n * (n - 1)) as f64
+            } else {
+                0.0
+            },
+            known_optimum: known_optimum(&self.name),
+        };
+        Ok(self.info.get_or_init(|| info).clone())
+    }
+
+    pub fn entry(&self) -> DatasetEntry {
+        match self.info() {
+            Ok(info) => DatasetEntry::Info(info),
+            Err(err) => DatasetEntry::Error {
+                name: self.name.clone(),
+                error: err.to_string(),
+            },
+        }
+    }
+}
+
+// 服务端目录中的图文件，按文件名索引；只能访问启动时扫描到的文件
+#[derive(Default)]
+pub struct Datasets {
+    datasets: BTreeMap<String, Dataset>,
+}
+
+impl Datasets {
+    // 目录不存在时为空，只收录能按扩展名判断格式的文件
+    pub fn scan(dir: &Path) -> Self {
+        let Ok(entries) = dir.read_dir() else {
+            return Datasets::default();
+        };
+        let datasets = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .to_str()
+                        .is_some_and(|path| Format::from_path(path).is_some())
+            })
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?.to_string();
+                let dataset = Dataset {
+                    path,
+                    name: name.clone(),
+                    info: OnceLock::new(),
+                };
+                Some((name, dataset))
+            })
+            .collect();
+        Datasets { datasets }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Dataset> {
+        self.datasets.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Dataset> {
+        self.datasets.values()
+    }
+}
@@ -0,0 +1,331 @@
+use clique::graph_reader::LabeledGraph;
+use clique::solver::{CancelToken, Progress, SolveStatus, SolverOptions, solve};
+use crossbeam::channel::{Receiver, Sender, unbounded};
+use petgraph::graph::{NodeIndex, UnGraph};
+use serde::Serialize;
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+// 已结束的任务保留一段时间供查询，提交新任务时清理过期的任务
+const RETENTION: Duration = Duration::from_secs(3600);
+
+pub type JobId = u64;
+
+pub enum JobGraph {
+    // JSON 请求中的边与孤立节点，节点编号任意
+    Edges {
+        nodes: Vec<usize>,
+        edges: Vec<(usize, usize)>,
+    },
+    // 服务端解析的图文件，保留孤立节点与原始标签
+    Labeled(LabeledGraph),
+}
+
+pub struct JobInput {
+    pub graph: JobGraph,
+    pub options: SolverOptions,
+    // 从任务开始运行时计时，排队的时间不算在内
+    pub time_limit: Option<Duration>,
+}
+
+// 结果中的节点标签：数字 ID 输出为 JSON 数字，其它（如 GraphML 的节点 id）输出为字符串
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Label {
+    Number(usize),
+    Name(String),
+}
+
+impl From<String> for Label {
+    fn from(label: String) -> Self {
+        // 只转换规范写法的数字，`007` 之类保持原样
+        match label.parse::<usize>() {
+            Ok(number) if number.to_string() == label => Label::Number(number),
+            _ => Label::Name(label),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobResult {
+    pub max_clique: Vec<Label>,
+    pub size: usize,
+    pub status: &'static str,
+    // 实际运行的算法，auto 已解析为具体算法
+    pub algorithm: &'static str,
+    pub elapsed_ms: f64,
+    pub optimal: bool,
+    // 已证明的团规模上界，最优时等于 size
+    pub upper_bound: Option<usize>,
+}
+
+// 求解过程中目前为止的最优团与已证明的上界
+#[derive(Debug, Clone, Serialize)]
+pub struct JobProgress {
+    pub clique: Vec<Label>,
+    pub size: usize,
+    pub upper_bound: usize,
+    pub elapsed_ms: f64,
+}
+
+impl JobProgress {
+    fn new(progress: &Progress, labels: &[Label]) -> Self {
+        JobProgress {
+            clique: progress.clique.iter().map(|&u| labels[u].clone()).collect(),
+            size: progress.clique.len(),
+            upper_bound: progress.upper_bound,
+            elapsed_ms: progress.elapsed.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum JobState {
+    Queued,
+    Running,
+    Done { result: JobResult },
+    // 运行中被取消时带上目前为止的最优解
+    Cancelled { result: Option<JobResult> },
+    // 求解过程中 panic，error 为 panic 信息
+    Failed { error: String },
+}
+
+impl JobState {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobState::Done { .. } | JobState::Cancelled { .. } | JobState::Failed { .. }
+        )
+    }
+}
+
+pub struct Job {
+    pub id: JobId,
+    input: Mutex<Option<JobInput>>,
+    cancel: CancelToken,
+    state: watch::Sender<JobState>,
+    progress: watch::Sender<Option<JobProgress>>,
+    finished_at: Mutex<Option<Instant>>,
+}
+
+impl Job {
+    fn new(id: JobId, input: JobInput) -> Self {
+        Job {
+            id,
+            input: Mutex::new(Some(input)),
+            cancel: CancelToken::new(),
+            state: watch::Sender::new(JobState::Queued),
+            progress: watch::Sender::new(None),
+            finished_at: Mutex::new(None),
+        }
+    }
+
+    pub fn state(&self) -> JobState {
+        self.state.borrow().clone()
+    }
+
+    pub fn progress(&self) -> Option<JobProgress> {
+        self.progress.borrow().clone()
+    }
+
+    // 订阅状态变化，用于等待任务结束
+    pub fn subscribe(&self) -> watch::Receiver<JobState> {
+        self.state.subscribe()
+    }
+
+    // 订阅进度，只保留最新的一次，读得慢的订阅者会跳过中间的进度
+    pub fn subscribe_progress(&self) -> watch::Receiver<Option<JobProgress>> {
+        self.progress.subscribe()
+    }
+
+    // 排队中的任务直接标记为取消；运行中的任务由求解器在下一个检查点停止
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+        let cancelled = self.state.send_if_modified(|state| match state {
+            JobState::Queued => {
+                *state = JobState::Cancelled { result: None };
+                true
+            }
+            _ => false,
+        });
+        if cancelled {
+            self.input.lock().unwrap().take();
+            self.mark_finished();
+        }
+    }
+
+    // 排队中的任务转为运行，返回其输入；已被取消的任务返回 None
+    fn start(&self) -> Option<JobInput> {
+        let started = self.state.send_if_modified(|state| match state {
+            JobState::Queued => {
+                *state = JobState::Running;
+                true
+            }
+            _ => false,
+        });
+        if started {
+            self.input.lock().unwrap().take()
+        } else {
+            None
+        }
+    }
+
+    fn run(&self, input: JobInput) {
+        let (graph, labels) = match input.graph {
+            JobGraph::Edges { nodes, edges } => build_graph(&nodes, &edges),
+            JobGraph::Labeled(labeled) => (
+                labeled.graph,
+                labeled.labels.into_iter().map(Label::from).collect(),
+            ),
+        };
+        let labels = Arc::new(labels);
+        let mut options = input
+            .options
+            .cancel_token(self.cancel.clone())
+            .on_progress({
+                let (sender, labels) = (self.progress.clone(), labels.clone());
+                move |progress| {
+                    sender.send_replace(Some(JobProgress::new(progress, &labels)));
+                }
+            });
+        if let Some(limit) = input.time_limit {
+            options = options.time_limit(limit);
+        }
+        let solution = solve(&graph, &options);
+        let max_clique: Vec<Label> = solution
+            .clique
+            .iter()
+            .map(|u| labels[u.index()].clone())
+            .collect();
+        let result = JobResult {
+            size: max_clique.len(),
+            max_clique,
+            status: solution.status.name(),
+            algorithm: solution.algorithm.name(),
+            elapsed_ms: solution.elapsed.as_secs_f64() * 1000.0,
+            optimal: solution.status.is_optimal(),
+            upper_bound: solution.upper_bound,
+        };
+        self.state.send_replace(match solution.status {
+            SolveStatus::Cancelled => JobState::Cancelled {
+                result: Some(result),
+            },
+            _ => JobState::Done { result },
+        });
+        self.mark_finished();
+    }
+
+    fn fail(&self, error: String) {
+        self.state.send_replace(JobState::Failed { error });
+        self.mark_finished();
+    }
+
+    fn mark_finished(&self) {
+        *self.finished_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    fn expired(&self, now: Instant) -> bool {
+        self.finished_at
+            .lock()
+            .unwrap()
+            .is_some_and(|at| now.duration_since(at) > RETENTION)
+    }
+}
+
+// 请求中的节点编号按大小映射为连续的 NodeIndex，labels[i] 为第 i 个节点的原编号
+fn build_graph(nodes: &[usize], edges: &[(usize, usize)]) -> (UnGraph<(), ()>, Vec<Label>) {
+    let mut labels: Vec<usize> = edges.iter().flat_map(|&(u, v)| [u, v]).collect();
+    labels.extend_from_slice(nodes);
+    labels.sort_unstable();
+    labels.dedup();
+    let index: HashMap<usize, NodeIndex> = labels
+        .iter()
+        .enumerate()
+        .map(|(i, &label)| (label, NodeIndex::new(i)))
+        .collect();
+
+    let mut graph = UnGraph::with_capacity(labels.len(), edges.len());
+    for _ in &labels {
+        graph.add_node(());
+    }
+    for (u, v) in edges {
+        graph.add_edge(index[u], index[v], ());
+    }
+    (graph, labels.into_iter().map(Label::Number).collect())
+}
+
+// 任务表与专用求解线程池：求解不占用 actix 的异步工作线程，
+// 同时运行的任务数不超过线程数，其余任务按提交顺序排队
+pub struct JobQueue {
+    jobs: Mutex<HashMap<JobId, Arc<Job>>>,
+    next_id: AtomicU64,
+    sender: Sender<Arc<Job>>,
+}
+
+impl JobQueue {
+    pub fn new(workers: usize) -> Self {
+        let (sender, receiver) = unbounded();
+        for i in 0..workers.max(1) {
+            let receiver = receiver.clone();
+            std::thread::Builder::new()
+                .name(format!("clique-solver-{i}"))
+                .spawn(move || run_worker(receiver))
+                .expect("failed to spawn solver thread");
+        }
+        JobQueue {
+            jobs: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            sender,
+        }
+    }
+
+    pub fn submit(&self, input: JobInput) -> Arc<Job> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let job = Arc::new(Job::new(id, input));
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            let now = Instant::now();
+            jobs.retain(|_, job| !job.expired(now));
+            jobs.insert(id, job.clone());
+        }
+        // 工作线程与队列同生命周期，发送不会失败
+        self.sender.send(job.clone()).unwrap();
+        job
+    }
+
+    pub fn get(&self, id: JobId) -> Option<Arc<Job>> {
+        self.jobs.lock().unwrap().get(&id).cloned()
+    }
+
+    pub fn remove(&self, id: JobId) -> Option<Arc<Job>> {
+        self.jobs.lock().unwrap().remove(&id)
+    }
+}
+
+// 求解中的 panic 只让当前任务失败，工作线程继续处理后面的任务
+fn run_worker(receiver: Receiver<Arc<Job>>) {
+    for job in receiver {
+        let Some(input) = job.start() else {
+            continue;
+        };
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| job.run(input))) {
+            job.fail(panic_message(payload.as_ref()));
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "solver panicked".into(),
+        },
+    }
+}
@@ -0,0 +1,485 @@
+mod datasets;
+mod jobs;
+mod options;
+
+use actix_cors::Cors;
+use actix_web::error::{InternalError, JsonPayloadError, QueryPayloadError};
+use actix_web::http::header;
+use actix_web::web::Bytes;
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, Responder, ResponseError, web};
+use clique::graph_reader::{Format, GraphErr, ReadOptions, read_graph_from_with};
+use clique::solver::SolverOptions;
+use datasets::{Dataset, DatasetInfo, Datasets};
+use futures_util::Stream;
+use futures_util::stream;
+use jobs::{Job, JobGraph, JobId, JobInput, JobProgress, JobQueue, JobState, Label};
+use options::{GaParams, SolveParams, solver_options};
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+// JSON 边列表与上传的图文件共用的大小限制
+const BODY_LIMIT: usize = 256 << 20;
+// 上传文件的 p 行等处声明的节点会预先创建，几十字节的请求就能声明数十亿个节点
+const MAX_VERTICES: usize = 1 << 22;
+// 没有事件时定期发送注释行，避免代理断开空闲连接
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+// 求解参数，都是可选的
+#[derive(Default, Deserialize)]
+struct SolveRequest {
+    // 与命令行 --algorithm 相同的名称，默认 auto
+    algorithm: Option<String>,
+    #[serde(default)]
+    options: SolveParams,
+    #[serde(default)]
+    ga_config: GaParams,
+}
+
+impl SolveRequest {
+    fn solver_options(&self) -> Result<(SolverOptions, Option<Duration>), String> {
+        solver_options(self.algorithm.as_deref(), &self.options, &self.ga_config)
+    }
+}
+
+#[derive(Deserialize)]
+struct GraphRequest {
+    edges: Vec<(usize, usize)>,
+    // 不在任何边上的孤立节点
+    #[serde(default)]
+    nodes: Vec<usize>,
+    #[serde(flatten)]
+    solve: SolveRequest,
+}
+
+impl GraphRequest {
+    fn into_input(self) -> Result<JobInput, String> {
+        let (options, time_limit) = self.solve.solver_options()?;
+        Ok(JobInput {
+            graph: JobGraph::Edges {
+                nodes: self.nodes,
+                edges: self.edges,
+            },
+            options,
+            time_limit,
+        })
+    }
+}
+
+// 上传文件时求解参数放在查询字符串中，名称与 JSON 请求中 options、ga_config 的字段相同
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UploadQuery {
+    // 格式名称，与命令行的 --input-format 相同
+    format: Option<String>,
+    // 原始文件名，没有指定 format 时按扩展名判断格式
+    name: Option<String>,
+    algorithm: Option<String>,
+    time_limit: Option<f64>,
+    seed: Option<u64>,
+    preprocess: Option<String>,
+    population_size: Option<usize>,
+    max_generations: Option<usize>,
+    shuffle_tolerance: Option<usize>,
+    local_improvement_iter: Option<usize>,
+}
+
+impl UploadQuery {
+    fn params(&self) -> (SolveParams, GaParams) {
+        let params = SolveParams {
+            time_limit: self.time_limit,
+            seed: self.seed,
+            preprocess: self.preprocess.clone(),
+        };
+        let ga = GaParams {
+            population_size: self.population_size,
+            max_generations: self.max_generations,
+            shuffle_tolerance: self.shuffle_tolerance,
+            local_improvement_iter: self.local_improvement_iter,
+        };
+        (params, ga)
+    }
+}
+
+#[derive(Serialize)]
+struct GraphResponse {
+    max_clique: Vec<Label>,
+}
+
+// 数据集的图，nodes 与 edges 可以直接作为 POST /api/jobs 的请求体
+#[derive(Serialize)]
+struct DatasetGraph {
+    info: DatasetInfo,
+    nodes: Vec<Label>,
+    edges: Vec<(Label, Label)>,
+}
+
+#[derive(Serialize)]
+struct JobView {
+    id: JobId,
+    #[serde(flatten)]
+    state: JobState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    progress: Option<JobProgress>,
+}
+
+impl JobView {
+    fn of(job: &Job) -> Self {
+        JobView {
+            id: job.id,
+            state: job.state(),
+            progress: job.progress(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+    // 图文件解析错误的位置（从 1 开始）与原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+}
+
+impl ErrorResponse {
+    fn new(error: impl ToString) -> Self {
+        ErrorResponse {
+            error: error.to_string(),
+            line: None,
+            column: None,
+            kind: None,
+        }
+    }
+}
+
+impl From<GraphErr> for ErrorResponse {
+    fn from(err: GraphErr) -> Self {
+        match &err {
+            GraphErr::ParseError { line, column, kind } => ErrorResponse {
+                error: err.to_string(),
+                line: Some(*line),
+                column: Some(*column),
+                kind: Some(kind.to_string()),
+            },
+            GraphErr::IoError(_) => ErrorResponse::new(err),
+        }
+    }
+}
+
+fn bad_request(error: impl ToString) -> HttpResponse {
+    HttpResponse::BadRequest().json(ErrorResponse::new(error))
+}
+
+fn job_not_found(id: JobId) -> HttpResponse {
+    HttpResponse::NotFound().json(ErrorResponse::new(format!("job {id} not found")))
+}
+
+// 请求体错误同样以 JSON 返回
+fn json_error(err: JsonPayloadError, _: &HttpRequest) -> actix_web::Error {
+    let response = HttpResponse::build(err.status_code()).json(ErrorResponse::new(&err));
+    InternalError::from_response(err, response).into()
+}
+
+fn query_error(err: QueryPayloadError, _: &HttpRequest) -> actix_web::Error {
+    let response = HttpResponse::build(err.status_code()).json(ErrorResponse::new(&err));
+    InternalError::from_response(err, response).into()
+}
+
+fn accepted(job: &Job) -> HttpResponse {
+    HttpResponse::Accepted()
+        .insert_header((header::LOCATION, format!("/api/jobs/{}", job.id)))
+        .json(JobView::of(job))
+}
+
+// 提交任务后立即返回任务编号，由客户端轮询结果
+async fn submit_job(data: web::Json<GraphRequest>, jobs: web::Data<JobQueue>) -> impl Responder {
+    match data.into_inner().into_input() {
+        Ok(input) => accepted(&jobs.submit(input)),
+        Err(error) => bad_request(error),
+    }
+}
+
+// 直接上传图文件（可以是压缩的），在阻塞线程池中用 graph_reader 解析，格式错误同步返回
+async fn upload_job(
+    query: web::Query<UploadQuery>,
+    body: Bytes,
+    jobs: web::Data<JobQueue>,
+    read_options: web::Data<ReadOptions>,
+) -> HttpResponse {
+    let format = match (&query.format, &query.name) {
+        (Some(name), _) => match Format::from_name(name) {
+            Some(format) => Some(format),
+            None => return bad_request(format!("unknown format `{name}`")),
+        },
+        (None, Some(name)) => Format::from_path(name),
+        (None, None) => None,
+    };
+    let (params, ga) = query.params();
+    let (options, time_limit) = match solver_options(query.algorithm.as_deref(), &params, &ga) {
+        Ok(options) => options,
+        Err(error) => return bad_request(error),
+    };
+    match web::block(move || read_graph_from_with(&body[..], format, &read_options)).await {
+        Ok(Ok(labeled)) => accepted(&jobs.submit(JobInput {
+            graph: JobGraph::Labeled(labeled),
+            options,
+            time_limit,
+        })),
+        Ok(Err(err)) => HttpResponse::BadRequest().json(ErrorResponse::from(err)),
+        Err(err) => HttpResponse::InternalServerError().json(ErrorResponse::new(err)),
+    }
+}
+
+async fn get_job(id: web::Path<JobId>, jobs: web::Data<JobQueue>) -> impl Responder {
+    let id = id.into_inner();
+    match jobs.get(id) {
+        Some(job) => HttpResponse::Ok().json(JobView::of(&job)),
+        None => job_not_found(id),
+    }
+}
+
+// Server-Sent Events：先发送当前状态与进度，之后每次变化推送一个事件，任务结束后关闭
+// event: state 的数据与 GET /api/jobs/{id} 相同，event: progress 的数据为 JobProgress
+async fn job_events(id: web::Path<JobId>, jobs: web::Data<JobQueue>) -> impl Responder {
+    let id = id.into_inner();
+    match jobs.get(id) {
+        Some(job) => HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .insert_header((header::CACHE_CONTROL, "no-cache"))
+            .streaming(event_stream(job)),
+        None => job_not_found(id),
+    }
+}
+
+fn sse(event: &str, data: &impl Serialize) -> Bytes {
+    // serde_json 的输出不含换行，可以直接作为一行 data
+    let data = serde_json::to_string(data).unwrap();
+    Bytes::from(format!("event: {event}\ndata: {data}\n\n"))
+}
+
+fn event_stream(job: Arc<Job>) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    let mut state = job.subscribe();
+    let mut progress = job.subscribe_progress();
+    state.mark_changed();
+    progress.mark_changed();
+    let keep_alive = tokio::time::interval_at(tokio::time::Instant::now() + KEEP_ALIVE, KEEP_ALIVE);
+
+    stream::unfold(
+        Some((job, state, progress, keep_alive)),
+        |events| async move {
+            let (job, mut state, mut progress, mut keep_alive) = events?;
+            loop {
+                // 同时有变化时先发送进度，保证最后的进度出现在结束状态之前
+                tokio::select! {
+                    biased;
+                    Ok(()) = progress.changed() => {
+                        let Some(latest) = progress.borrow_and_update().clone() else {
+                            continue;
+                        };
+                        let event = sse("progress", &latest);
+                        return Some((Ok(event), Some((job, state, progress, keep_alive))));
+                    }
+                    Ok(()) = state.changed() => {
+                        let finished = state.borrow_and_update().is_finished();
+                        let event = sse("state", &JobView::of(&job));
+                        let next = (!finished).then_some((job, state, progress, keep_alive));
+                        return Some((Ok(event), next));
+                    }
+                    _ = keep_alive.tick() => {
+                        let event = Bytes::from_static(b": keep-alive\n\n");
+                        return Some((Ok(event), Some((job, state, progress, keep_alive))));
+                    }
+                }
+            }
+        },
+    )
+}
+
+fn dataset_not_found(name: &str) -> HttpResponse {
+    HttpResponse::NotFound().json(ErrorResponse::new(format!("dataset `{name}` not found")))
+}
+
+// 服务端自带的基准图：规模、密度与已知最优解，第一次请求时解析全部文件
+async fn list_datasets(datasets: web::Data<Datasets>) -> HttpResponse {
+    let listed = web::block(move || datasets.iter().map(Dataset::entry).collect::<Vec<_>>()).await;
+    match listed {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(err) => HttpResponse::InternalServerError().json(ErrorResponse::new(err)),
+    }
+}
+
+async fn get_dataset(name: web::Path<String>, datasets: web::Data<Datasets>) -> HttpResponse {
+    let name = name.into_inner();
+    if datasets.get(&name).is_none() {
+        return dataset_not_found(&name);
+    }
+    let loaded = web::block(move || {
+        let dataset = datasets.get(&name).unwrap();
+        let info = dataset.info()?;
+        let labeled = dataset.load()?;
+        let label = |u: NodeIndex| Label::from(labeled.label(u).to_string());
+        let edges = labeled
+            .graph
+            .raw_edges()
+            .iter()
+            .map(|e| (label(e.source()), label(e.target())))
+            .collect();
+        let nodes = labeled.labels.into_iter().map(Label::from).collect();
+        Ok::<_, GraphErr>(DatasetGraph { info, nodes, edges })
+    })
+    .await;
+    match loaded {
+        Ok(Ok(graph)) => HttpResponse::Ok().json(graph),
+        Ok(Err(err)) => HttpResponse::InternalServerError().json(ErrorResponse::from(err)),
+        Err(err) => HttpResponse::InternalServerError().json(ErrorResponse::new(err)),
+    }
+}
+
+// 请求体为可选的求解参数（与 POST /api/jobs 中的 algorithm、options、ga_config 相同）
+async fn solve_dataset(
+    name: web::Path<String>,
+    body: Bytes,
+    datasets: web::Data<Datasets>,
+    jobs: web::Data<JobQueue>,
+) -> HttpResponse {
+    let name = name.into_inner();
+    if datasets.get(&name).is_none() {
+        return dataset_not_found(&name);
+    }
+    let request = if body.is_empty() {
+        SolveRequest::default()
+    } else {
+        match serde_json::from_slice::<SolveRequest>(&body) {
+            Ok(request) => request,
+            Err(err) => return bad_request(err),
+        }
+    };
+    let (options, time_limit) = match request.solver_options() {
+        Ok(options) => options,
+        Err(error) => return bad_request(error),
+    };
+    match web::block(move || datasets.get(&name).unwrap().load()).await {
+        Ok(Ok(labeled)) => accepted(&jobs.submit(JobInput {
+            graph: JobGraph::Labeled(labeled),
+            options,
+            time_limit,
+        })),
+        Ok(Err(err)) => HttpResponse::InternalServerError().json(ErrorResponse::from(err)),
+        Err(err) => HttpResponse::InternalServerError().json(ErrorResponse::new(err)),
+    }
+}
+
+// 未结束的任务被取消；已结束的任务从任务表中删除
+async fn cancel_job(id: web::Path<JobId>, jobs: web::Data<JobQueue>) -> impl Responder {
+    let id = id.into_inner();
+    let Some(job) = jobs.get(id) else {
+        return job_not_found(id);
+    };
+    if job.state().is_finished() {
+        jobs.remove(id);
+        return HttpResponse::NoContent().finish();
+    }
+    job.cancel();
+    HttpResponse::Ok().json(JobView::of(&job))
+}
+
+// 客户端断开时取消仍在运行的任务
+struct CancelOnDrop(Arc<Job>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if !self.0.state().is_finished() {
+            self.0.cancel();
+        }
+    }
+}
+
+// 旧的同步接口：同样经过任务队列，在不占用工作线程的情况下等待结果
+async fn find_max_clique(
+    data: web::Json<GraphRequest>,
+    jobs: web::Data<JobQueue>,
+) -> impl Responder {
+    let input = match data.into_inner().into_input() {
+        Ok(input) => input,
+        Err(error) => return bad_request(error),
+    };
+    let job = jobs.submit(input);
+    let guard = CancelOnDrop(job.clone());
+    let mut state = job.subscribe();
+    // 发送端由 job 持有，等待不会失败
+    let finished = state.wait_for(JobState::is_finished).await.unwrap().clone();
+    drop(guard);
+    jobs.remove(job.id);
+
+    let max_clique = match finished {
+        JobState::Done { result } => result.max_clique,
+        JobState::Failed { error } => {
+            return HttpResponse::InternalServerError().json(ErrorResponse::new(error));
+        }
+        _ => Vec::new(),
+    };
+    HttpResponse::Ok().json(GraphResponse { max_clique })
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    // CLIQUE_API_ADDR 指定监听地址，CLIQUE_WORKERS 指定同时运行的求解任务数，
+    // CLIQUE_DATA_DIR 指定数据集目录，CLIQUE_MAX_VERTICES 指定上传文件允许声明的最大节点数
+    let addr = std::env::var("CLIQUE_API_ADDR").unwrap_or_else(|_| "127.0.0.1:8080".into());
+    let workers = std::env::var("CLIQUE_WORKERS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, usize::from));
+    let jobs = web::Data::new(JobQueue::new(workers));
+    let data_dir = std::env::var("CLIQUE_DATA_DIR").unwrap_or_else(|_| "data".into());
+    let datasets = web::Data::new(Datasets::scan(Path::new(&data_dir)));
+    let max_vertices = std::env::var("CLIQUE_MAX_VERTICES")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(MAX_VERTICES);
+    let read_options = web::Data::new(ReadOptions::new().max_vertices(max_vertices));
+
+    let server = HttpServer::new(move || {
+        let cors = Cors::default()
+            .allow_any_origin()
+            .allow_any_method()
+            .allow_any_header()
+            .expose_headers([header::LOCATION])
+            .max_age(3600);
+
+        App::new()
+            .wrap(cors)
+            .app_data(jobs.clone())
+            .app_data(datasets.clone())
+            .app_data(read_options.clone())
+            // 大规模图的边列表远超默认的 32KB
+            .app_data(
+                web::JsonConfig::default()
+                    .limit(BODY_LIMIT)
+                    .error_handler(json_error),
+            )
+            .app_data(web::QueryConfig::default().error_handler(query_error))
+            .app_data(web::PayloadConfig::new(BODY_LIMIT))
+            .route("/api/jobs", web::post().to(submit_job))
+            .route("/api/jobs/upload", web::post().to(upload_job))
+            .route("/api/jobs/{id}", web::get().to(get_job))
+            .route("/api/jobs/{id}", web::delete().to(cancel_job))
+            .route("/api/jobs/{id}/events", web::get().to(job_events))
+            .route("/api/datasets", web::get().to(list_datasets))
+            .route("/api/datasets/{name}", web::get().to(get_dataset))
+            .route("/api/datasets/{name}/solve", web::post().to(solve_dataset))
+            .route("/api/find-max-clique", web::post().to(find_max_clique))
+    })
+    .bind(addr)?;
+    for addr in server.addrs() {
+        eprintln!("listening on {addr}");
+    }
+    server.run().await
+}
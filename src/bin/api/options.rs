@@ -0,0 +1,81 @@
+use clique::ga::GAConfig;
+use clique::max_clique::Algorithm;
+use clique::preprocess::Preprocess;
+use clique::solver::SolverOptions;
+use serde::Deserialize;
+use std::time::Duration;
+
+// 请求中的求解参数，与命令行的 --time-limit、--seed、--preprocess 对应
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SolveParams {
+    // 秒，从任务开始运行时计时，排队的时间不算在内
+    pub time_limit: Option<f64>,
+    // 遗传算法（包括 portfolio 与 genetic 预处理）的随机种子，设置后结果可复现
+    pub seed: Option<u64>,
+    // off / greedy / genetic，默认 greedy
+    pub preprocess: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GaParams {
+    pub population_size: Option<usize>,
+    pub max_generations: Option<usize>,
+    pub shuffle_tolerance: Option<usize>,
+    pub local_improvement_iter: Option<usize>,
+}
+
+impl GaParams {
+    fn config(&self, seed: Option<u64>) -> GAConfig {
+        let mut config = GAConfig::new();
+        if let Some(n) = self.population_size {
+            config = config.population_size(n);
+        }
+        if let Some(n) = self.max_generations {
+            config = config.max_generations(n);
+        }
+        if let Some(n) = self.shuffle_tolerance {
+            config = config.shuffle_tolerance(n);
+        }
+        if let Some(n) = self.local_improvement_iter {
+            config = config.local_improvement_iter(n);
+        }
+        if let Some(seed) = seed {
+            config = config.seed(seed);
+        }
+        config
+    }
+}
+
+// 求解参数与时间限制分开返回，截止时间在任务开始运行时才计算
+pub fn solver_options(
+    algorithm: Option<&str>,
+    params: &SolveParams,
+    ga: &GaParams,
+) -> Result<(SolverOptions, Option<Duration>), String> {
+    let algorithm = match algorithm {
+        Some(name) => {
+            Algorithm::from_name(name).ok_or_else(|| format!("unknown algorithm `{name}`"))?
+        }
+        None => Algorithm::Auto,
+    };
+    let preprocess = match &params.preprocess {
+        Some(name) => Preprocess::from_name(name)
+            .ok_or_else(|| format!("unknown preprocess mode `{name}`"))?,
+        None => Preprocess::Greedy,
+    };
+    // 负数、NaN 与超出 Duration 范围的值都拒绝；超出 Instant 范围的时间限制等同于不限时
+    let time_limit = match params.time_limit {
+        Some(secs) => Some(
+            Duration::try_from_secs_f64(secs)
+                .map_err(|_| format!("invalid time_limit `{secs}`"))?,
+        ),
+        None => None,
+    };
+    let options = SolverOptions::new()
+        .algorithm(algorithm)
+        .preprocess(preprocess)
+        .ga_config(ga.config(params.seed));
+    Ok((options, time_limit))
+}
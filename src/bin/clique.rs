@@ -0,0 +1,692 @@
+use clique::ga::GAConfig;
+use clique::graph_reader::{
+    DimacsOptions, Format, GraphErr, LabeledGraph, decompress, read_dimacs_file_from, read_graph,
+    read_graph_as, read_graph_from,
+};
+use clique::graph_writer::{
+    write_dimacs, write_dimacs_binary, write_dot, write_edge_list, write_graphml, write_metis,
+};
+use clique::max_clique::Algorithm;
+use clique::preprocess::{Preprocess, core_numbers, greedy_clique};
+use clique::solver::{SolverOptions, solve};
+use clique::verify::{VerifyError, check_clique, check_maximal};
+use petgraph::algo::connected_components;
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+const USAGE: &str = "\
+usage: clique <command> [options]
+
+commands:
+  solve <file>              find a maximum clique, print its node labels
+  stats <file>              print size, density, degeneracy and clique bounds
+  verify <file> <solution>  check that the solution is a clique
+  convert <in> <out>        convert between graph formats
+  bench <dir>               solve every graph file in a directory
+
+options:
+  -a, --algorithm <name>    auto, bk, mcs, parallel-mcs, sparse, genetic, portfolio (default auto)
+  -t, --time-limit <secs>   stop and report the best clique found so far
+  -s, --seed <n>            seed for the genetic algorithm
+  -p, --preprocess <mode>   off, greedy, genetic (default greedy)
+  -f, --format <fmt>        solve: text, json, dot; stats/bench: text, json (default text)
+  -i, --input-format <fmt>  dimacs, snap, metis, mtx, graphml, pace (default: by extension/content)
+  -o, --output-format <fmt> convert: dimacs, dimacs-binary, edges, metis, graphml, dot
+      --maximal             verify: also require the clique to be maximal
+
+`-` reads the graph or solution from stdin (and writes `convert` output to stdout).
+exit status: 0 on success, 1 on read, parse or verification errors, 2 on usage errors";
+
+// 用法错误退出码为 2，其余（读取、解析、验证失败）为 1
+#[derive(Error, Debug)]
+enum CliError {
+    #[error("{0}")]
+    Usage(String),
+    #[error("{path}: {source}")]
+    Graph { path: String, source: GraphErr },
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+fn usage(msg: impl Into<String>) -> CliError {
+    CliError::Usage(msg.into())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => code,
+        Err(CliError::Usage(msg)) => {
+            eprintln!("error: {msg}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode, CliError> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(usage("missing command"));
+    };
+    let args = Args::parse(rest)?;
+    match command.as_str() {
+        "solve" => solve_command(args),
+        "stats" => stats_command(args),
+        "verify" => verify_command(args),
+        "convert" => convert_command(args),
+        "bench" => bench_command(args),
+        "help" | "-h" | "--help" => {
+            println!("{USAGE}");
+            Ok(ExitCode::SUCCESS)
+        }
+        other => Err(usage(format!("unknown command `{other}`"))),
+    }
+}
+
+// 不带值的选项
+const FLAGS: [&str; 1] = ["maximal"];
+
+// 位置参数与 `--name value` / `--name=value` 形式的选项
+struct Args {
+    positional: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Self, CliError> {
+        let mut parsed = Args {
+            positional: Vec::new(),
+            options: Vec::new(),
+        };
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let name = match arg.strip_prefix("--") {
+                Some(long) => long,
+                None if arg.len() == 2 && arg.starts_with('-') => short_option(arg)?,
+                None => {
+                    parsed.positional.push(arg.clone());
+                    continue;
+                }
+            };
+            let (name, value) = match name.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None if FLAGS.contains(&name) => (name, None),
+                None => {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| usage(format!("option --{name} needs a value")))?;
+                    (name, Some(value.clone()))
+                }
+            };
+            parsed.options.push((name.to_string(), value));
+        }
+        Ok(parsed)
+    }
+
+    fn take(&mut self, name: &str) -> Option<String> {
+        let i = self.options.iter().position(|(n, _)| n == name)?;
+        self.options.remove(i).1
+    }
+
+    fn flag(&mut self, name: &str) -> bool {
+        let i = self.options.iter().position(|(n, _)| n == name);
+        i.map(|i| self.options.remove(i)).is_some()
+    }
+
+    // 取出全部位置参数并检查没有多余的选项
+    fn finish<const N: usize>(self, names: [&str; N]) -> Result<[String; N], CliError> {
+        if let Some((name, _)) = self.options.first() {
+            return Err(usage(format!("unknown option --{name}")));
+        }
+        let count = self.positional.len();
+        self.positional.try_into().map_err(|_| {
+            usage(format!(
+                "expected {}, got {count} arguments",
+                names.join(" ")
+            ))
+        })
+    }
+}
+
+fn short_option(arg: &str) -> Result<&'static str, CliError> {
+    Ok(match arg {
+        "-a" => "algorithm",
+        "-t" => "time-limit",
+        "-s" => "seed",
+        "-p" => "preprocess",
+        "-f" => "format",
+        "-i" => "input-format",
+        "-o" => "output-format",
+        _ => return Err(usage(format!("unknown option {arg}"))),
+    })
+}
+
+fn parse_algorithm(name: &str) -> Result<Algorithm, CliError> {
+    Algorithm::from_name(name).ok_or_else(|| usage(format!("unknown algorithm `{name}`")))
+}
+
+fn parse_input_format(name: &str) -> Result<Format, CliError> {
+    Format::from_name(name).ok_or_else(|| usage(format!("unknown input format `{name}`")))
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, CliError> {
+    value
+        .parse()
+        .map_err(|_| usage(format!("invalid value `{value}` for --{name}")))
+}
+
+// solve 与 bench 共用的求解参数，截止时间在每次求解前才开始计算
+struct SolveArgs {
+    algorithm: Algorithm,
+    time_limit: Option<Duration>,
+    ga: GAConfig,
+    preprocess: Preprocess,
+}
+
+impl SolveArgs {
+    fn take(args: &mut Args) -> Result<Self, CliError> {
+        let algorithm = match args.take("algorithm") {
+            Some(name) => parse_algorithm(&name)?,
+            None => Algorithm::Auto,
+        };
+        let time_limit = match args.take("time-limit") {
+            Some(value) => {
+                // 负数、NaN 与超出 Duration 范围的值都是用法错误
+                let secs: f64 = parse_value("time-limit", &value)?;
+                let limit = Duration::try_from_secs_f64(secs)
+                    .map_err(|_| usage(format!("invalid value `{value}` for --time-limit")))?;
+                Some(limit)
+            }
+            None => None,
+        };
+        let mut ga = GAConfig::new();
+        if let Some(value) = args.take("seed") {
+            ga = ga.seed(parse_value("seed", &value)?);
+        }
+        let preprocess = match args.take("preprocess") {
+            Some(name) => Preprocess::from_name(&name)
+                .ok_or_else(|| usage(format!("unknown preprocess mode `{name}`")))?,
+            None => Preprocess::Greedy,
+        };
+        Ok(SolveArgs {
+            algorithm,
+            time_limit,
+            ga,
+            preprocess,
+        })
+    }
+
+    fn options(&self) -> SolverOptions {
+        let mut options = SolverOptions::new()
+            .algorithm(self.algorithm)
+            .ga_config(self.ga.clone())
+            .preprocess(self.preprocess);
+        if let Some(limit) = self.time_limit {
+            options = options.time_limit(limit);
+        }
+        options
+    }
+}
+
+fn take_input_format(args: &mut Args) -> Result<Option<Format>, CliError> {
+    args.take("input-format")
+        .map(|name| parse_input_format(&name))
+        .transpose()
+}
+
+// `-` 从标准输入读取，此时没有扩展名可用，格式根据内容判断
+fn load(path: &str, format: Option<Format>) -> Result<LabeledGraph, CliError> {
+    let labeled = match (path, format) {
+        ("-", format) => read_graph_from(io::stdin().lock(), format),
+        (path, Some(format)) => read_graph_as(path, format),
+        (path, None) => read_graph(path),
+    };
+    labeled.map_err(|source| CliError::Graph {
+        path: path.to_string(),
+        source,
+    })
+}
+
+// 与 load 相同，DIMACS 输入额外返回注释行，供 convert 写回
+fn load_with_comments(
+    path: &str,
+    format: Option<Format>,
+) -> Result<(LabeledGraph, Vec<String>), CliError> {
+    let read = || -> Result<_, GraphErr> {
+        let input: Box<dyn BufRead> = if path == "-" {
+            Box::new(io::stdin().lock())
+        } else {
+            Box::new(BufReader::new(File::open(path)?))
+        };
+        let mut reader = decompress(input)?;
+        let format = match format.or_else(|| Format::from_path(path)) {
+            Some(format) => format,
+            None => Format::detect(reader.fill_buf()?),
+        };
+        if format != Format::Dimacs {
+            return Ok((read_graph_from(reader, Some(format))?, Vec::new()));
+        }
+        let file = read_dimacs_file_from(reader, &DimacsOptions::default())?;
+        let labels = (1..=file.graph.node_count())
+            .map(|i| i.to_string())
+            .collect();
+        let labeled = LabeledGraph {
+            graph: file.graph,
+            labels,
+        };
+        Ok((labeled, file.comments))
+    };
+    read().map_err(|source| CliError::Graph {
+        path: path.to_string(),
+        source,
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Report {
+    Text,
+    Json,
+    Dot,
+}
+
+fn take_report(args: &mut Args, allowed: &[Report]) -> Result<Report, CliError> {
+    let report = match args.take("format").as_deref() {
+        None | Some("text") => Report::Text,
+        Some("json") => Report::Json,
+        Some("dot") => Report::Dot,
+        Some(other) => return Err(usage(format!("unknown output format `{other}`"))),
+    };
+    if !allowed.contains(&report) {
+        return Err(usage("this output format is not supported by the command"));
+    }
+    Ok(report)
+}
+
+#[derive(Serialize)]
+struct SolveReport<'a> {
+    size: usize,
+    clique: Vec<&'a str>,
+    status: &'static str,
+    algorithm: &'static str,
+    elapsed_ms: f64,
+    upper_bound: Option<usize>,
+}
+
+// 文本格式：标准输出只有团的节点标签（可以直接交给 verify），摘要写到标准错误
+fn solve_command(mut args: Args) -> Result<ExitCode, CliError> {
+    let solve_args = SolveArgs::take(&mut args)?;
+    let input_format = take_input_format(&mut args)?;
+    let report = take_report(&mut args, &[Report::Text, Report::Json, Report::Dot])?;
+    let [path] = args.finish(["<file>"])?;
+
+    let labeled = load(&path, input_format)?;
+    let solution = solve(&labeled.graph, &solve_args.options());
+    let mut clique = solution.clique.clone();
+    clique.sort();
+
+    let mut stdout = io::stdout().lock();
+    match report {
+        Report::Text => {
+            writeln!(stdout, "{}", labeled.labels_of(&clique).join(" "))?;
+            eprintln!(
+                "size {}, {}, upper bound {}, algorithm {}, {:.3}s",
+                clique.len(),
+                solution.status.name(),
+                solution.upper_bound.unwrap_or(clique.len()),
+                solution.algorithm.name(),
+                solution.elapsed.as_secs_f64()
+            );
+        }
+        Report::Json => {
+            let report = SolveReport {
+                size: clique.len(),
+                clique: labeled.labels_of(&clique),
+                status: solution.status.name(),
+                algorithm: solution.algorithm.name(),
+                elapsed_ms: solution.elapsed.as_secs_f64() * 1000.0,
+                upper_bound: solution.upper_bound,
+            };
+            serde_json::to_writer(&mut stdout, &report).map_err(io::Error::from)?;
+            writeln!(stdout)?;
+        }
+        Report::Dot => write_dot(&labeled.graph, &clique, stdout)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Serialize)]
+struct Stats {
+    nodes: usize,
+    edges: usize,
+    density: f64,
+    min_degree: usize,
+    max_degree: usize,
+    avg_degree: f64,
+    components: usize,
+    degeneracy: usize,
+    // 贪心团的规模与退化度 + 1，最大团的规模介于两者之间
+    clique_lower_bound: usize,
+    clique_upper_bound: usize,
+}
+
+fn stats_command(mut args: Args) -> Result<ExitCode, CliError> {
+    let input_format = take_input_format(&mut args)?;
+    let report = take_report(&mut args, &[Report::Text, Report::Json])?;
+    let [path] = args.finish(["<file>"])?;
+
+    let graph = load(&path, input_format)?.graph;
+    let (n, m) = (graph.node_count(), graph.edge_count());
+    let degrees: Vec<usize> = graph
+        .node_indices()
+        .map(|u| graph.neighbors(u).count())
+        .collect();
+    let degeneracy = core_numbers(&graph).into_iter().max().unwrap_or(0);
+    let stats = Stats {
+        nodes: n,
+        edges: m,
+        density: if n > 1 {
+            2.0 * m as f64 / (n * (n - 1)) as f64
+        } else {
+            0.0
+        },
+        min_degree: degrees.iter().copied().min().unwrap_or(0),
+        max_degree: degrees.iter().copied().max().unwrap_or(0),
+        avg_degree: if n > 0 {
+            2.0 * m as f64 / n as f64
+        } else {
+            0.0
+        },
+        components: connected_components(&graph),
+        degeneracy,
+        clique_lower_bound: greedy_clique(&graph).len(),
+        clique_upper_bound: if n > 0 { degeneracy + 1 } else { 0 },
+    };
+
+    let mut stdout = io::stdout().lock();
+    match report {
+        Report::Json => {
+            serde_json::to_writer(&mut stdout, &stats).map_err(io::Error::from)?;
+            writeln!(stdout)?;
+        }
+        _ => {
+            writeln!(stdout, "nodes              {}", stats.nodes)?;
+            writeln!(stdout, "edges              {}", stats.edges)?;
+            writeln!(stdout, "density            {:.6}", stats.density)?;
+            writeln!(stdout, "min degree         {}", stats.min_degree)?;
+            writeln!(stdout, "max degree         {}", stats.max_degree)?;
+            writeln!(stdout, "avg degree         {:.3}", stats.avg_degree)?;
+            writeln!(stdout, "components         {}", stats.components)?;
+            writeln!(stdout, "degeneracy         {}", stats.degeneracy)?;
+            writeln!(stdout, "clique lower bound {}", stats.clique_lower_bound)?;
+            writeln!(stdout, "clique upper bound {}", stats.clique_upper_bound)?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+// 解的格式：空白分隔的节点标签（`#` 开头的行为注释），或 solve --format json 的输出
+fn read_solution(path: &str) -> Result<Vec<String>, CliError> {
+    let mut text = String::new();
+    if path == "-" {
+        io::stdin().lock().read_to_string(&mut text)?;
+    } else {
+        BufReader::new(File::open(path)?).read_to_string(&mut text)?;
+    }
+
+    let trimmed = text.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with('[') {
+        let invalid = || CliError::Invalid(format!("{path}: expected a list of node labels"));
+        let value: serde_json::Value = serde_json::from_str(trimmed)
+            .map_err(|err| CliError::Invalid(format!("{path}: {err}")))?;
+        let nodes = match &value {
+            serde_json::Value::Object(object) => object.get("clique").ok_or_else(invalid)?,
+            array => array,
+        };
+        return nodes
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|node| match node {
+                serde_json::Value::String(label) => Ok(label.clone()),
+                serde_json::Value::Number(label) => Ok(label.to_string()),
+                _ => Err(invalid()),
+            })
+            .collect();
+    }
+
+    Ok(text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(str::split_whitespace)
+        .map(String::from)
+        .collect())
+}
+
+fn verify_command(mut args: Args) -> Result<ExitCode, CliError> {
+    let input_format = take_input_format(&mut args)?;
+    let require_maximal = args.flag("maximal");
+    let [graph_path, solution_path] = args.finish(["<file>", "<solution>"])?;
+
+    let labeled = load(&graph_path, input_format)?;
+    let index: HashMap<&str, NodeIndex> = labeled
+        .labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| (label.as_str(), NodeIndex::new(i)))
+        .collect();
+    let clique = read_solution(&solution_path)?
+        .iter()
+        .map(|label| {
+            index
+                .get(label.as_str())
+                .copied()
+                .ok_or_else(|| CliError::Invalid(format!("node `{label}` is not in the graph")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // 错误信息使用源文件中的节点标签
+    let describe = |err: VerifyError| {
+        CliError::Invalid(match err {
+            VerifyError::NodeOutOfRange(u) => format!("node {} is not in the graph", u.index()),
+            VerifyError::DuplicateNode(u) => {
+                format!("node `{}` appears more than once", labeled.label(u))
+            }
+            VerifyError::NotAdjacent(u, v) => format!(
+                "not a clique: nodes `{}` and `{}` are not adjacent",
+                labeled.label(u),
+                labeled.label(v)
+            ),
+            VerifyError::NotMaximal(u) => {
+                format!("not maximal: node `{}` can be added", labeled.label(u))
+            }
+            VerifyError::InvalidProof(msg) => msg,
+        })
+    };
+    check_clique(&labeled.graph, &clique).map_err(describe)?;
+    let maximal = check_maximal(&labeled.graph, &clique);
+    let note = match &maximal {
+        Ok(()) => "maximal".to_string(),
+        Err(VerifyError::NotMaximal(u)) => {
+            format!("not maximal, node `{}` can be added", labeled.label(*u))
+        }
+        Err(_) => "not maximal".to_string(),
+    };
+    if require_maximal {
+        maximal.map_err(describe)?;
+    }
+    println!("ok: clique of size {} ({note})", clique.len());
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Clone, Copy)]
+enum OutputGraph {
+    Dimacs,
+    DimacsBinary,
+    EdgeList,
+    Metis,
+    GraphMl,
+    Dot,
+}
+
+impl OutputGraph {
+    fn parse(name: &str) -> Result<Self, CliError> {
+        Ok(match name {
+            "dimacs" => OutputGraph::Dimacs,
+            "dimacs-binary" => OutputGraph::DimacsBinary,
+            "edges" | "snap" => OutputGraph::EdgeList,
+            "metis" => OutputGraph::Metis,
+            "graphml" => OutputGraph::GraphMl,
+            "dot" => OutputGraph::Dot,
+            _ => return Err(usage(format!("unknown output format `{name}`"))),
+        })
+    }
+
+    fn from_path(path: &str) -> Option<Self> {
+        let path = path.to_ascii_lowercase();
+        let ext = path.rsplit_once('.')?.1;
+        Some(match ext {
+            "clq" | "col" | "dimacs" => OutputGraph::Dimacs,
+            "b" => OutputGraph::DimacsBinary,
+            "txt" | "edges" | "el" | "tsv" | "snap" => OutputGraph::EdgeList,
+            "graph" | "metis" => OutputGraph::Metis,
+            "graphml" | "xml" => OutputGraph::GraphMl,
+            "dot" | "gv" => OutputGraph::Dot,
+            _ => return None,
+        })
+    }
+}
+
+// 输出只保留图结构与 DIMACS 的注释行，节点按输入中的顺序重新编号
+fn convert_command(mut args: Args) -> Result<ExitCode, CliError> {
+    let input_format = take_input_format(&mut args)?;
+    let output_format = args.take("output-format");
+    let [input, output] = args.finish(["<in>", "<out>"])?;
+    let format = match output_format {
+        Some(name) => OutputGraph::parse(&name)?,
+        None => OutputGraph::from_path(&output).ok_or_else(|| {
+            usage(format!(
+                "cannot tell the output format of `{output}`, use --output-format"
+            ))
+        })?,
+    };
+
+    let (labeled, comments) = load_with_comments(&input, input_format)?;
+    let graph = labeled.graph;
+    let writer: Box<dyn Write> = if output == "-" {
+        Box::new(io::stdout().lock())
+    } else {
+        Box::new(BufWriter::new(File::create(&output)?))
+    };
+    match format {
+        OutputGraph::Dimacs => write_dimacs(&graph, &comments, writer)?,
+        OutputGraph::DimacsBinary => write_dimacs_binary(&graph, writer)?,
+        OutputGraph::EdgeList => write_edge_list(&graph, writer)?,
+        OutputGraph::Metis => write_metis(&graph, writer)?,
+        OutputGraph::GraphMl => write_graphml(&graph, writer)?,
+        OutputGraph::Dot => write_dot(&graph, &[], writer)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[derive(Serialize)]
+struct BenchRow {
+    file: String,
+    nodes: usize,
+    edges: usize,
+    size: usize,
+    status: &'static str,
+    algorithm: &'static str,
+    load_ms: f64,
+    solve_ms: f64,
+}
+
+// 按文件名顺序求解目录中所有能识别扩展名的图文件
+// 读取失败的文件报告到标准错误并继续，最后以非零状态退出
+fn bench_command(mut args: Args) -> Result<ExitCode, CliError> {
+    let solve_args = SolveArgs::take(&mut args)?;
+    let input_format = take_input_format(&mut args)?;
+    let report = take_report(&mut args, &[Report::Text, Report::Json])?;
+    let [dir] = args.finish(["<dir>"])?;
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .to_str()
+                    .is_some_and(|path| Format::from_path(path).is_some())
+        })
+        .collect();
+    files.sort();
+
+    let mut stdout = io::stdout().lock();
+    if report == Report::Text {
+        writeln!(
+            stdout,
+            "{:<28} {:>9} {:>11} {:>6} {:>10} {:>12} {:>10}",
+            "file", "nodes", "edges", "size", "status", "algorithm", "time"
+        )?;
+    }
+    let mut failed = 0;
+    for path in files {
+        let file = path.file_name().unwrap().to_string_lossy().into_owned();
+        let start = Instant::now();
+        let labeled = match load(path.to_str().unwrap(), input_format) {
+            Ok(labeled) => labeled,
+            Err(err) => {
+                eprintln!("error: {err}");
+                failed += 1;
+                continue;
+            }
+        };
+        let load_time = start.elapsed();
+        let solution = solve(&labeled.graph, &solve_args.options());
+        let row = BenchRow {
+            file,
+            nodes: labeled.graph.node_count(),
+            edges: labeled.graph.edge_count(),
+            size: solution.clique.len(),
+            status: solution.status.name(),
+            algorithm: solution.algorithm.name(),
+            load_ms: load_time.as_secs_f64() * 1000.0,
+            solve_ms: solution.elapsed.as_secs_f64() * 1000.0,
+        };
+        match report {
+            // 每行一个 JSON 对象，便于流式处理
+            Report::Json => {
+                serde_json::to_writer(&mut stdout, &row).map_err(io::Error::from)?;
+                writeln!(stdout)?;
+            }
+            _ => writeln!(
+                stdout,
+                "{:<28} {:>9} {:>11} {:>6} {:>10} {:>12} {:>9.3}s",
+                row.file,
+                row.nodes,
+                row.edges,
+                row.size,
+                row.status,
+                row.algorithm,
+                row.solve_ms / 1000.0
+            )?,
+        }
+        stdout.flush()?;
+    }
+
+    Ok(if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
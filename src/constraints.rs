@@ -0,0 +1,200 @@
+use bitvec::prelude::*;
+use fixedbitset::FixedBitSet;
+use petgraph::graph::NodeIndex;
+use thiserror::Error;
+
+use crate::ga::{GAConfig, Restriction, find_max_clique_with_ga_restricted};
+use crate::graph::{CliqueGraph, Compact, induced_adjacency};
+use crate::max_clique::{Algorithm, find_max_clique_with_bk_restricted};
+use crate::solver::{SolverOptions, solve_compact_from};
+use crate::verify::{VerifyError, check_clique};
+
+// 带约束的最大团：结果必须包含 must_include 中的全部节点、不含 must_exclude 中的节点
+// initial_solution 为已知满足约束的团，作为初始下界（热启动）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraints<Id = NodeIndex> {
+    pub must_include: Vec<Id>,
+    pub must_exclude: Vec<Id>,
+    pub initial_solution: Option<Vec<Id>>,
+}
+
+impl<Id> Default for Constraints<Id> {
+    fn default() -> Self {
+        Constraints {
+            must_include: Vec::new(),
+            must_exclude: Vec::new(),
+            initial_solution: None,
+        }
+    }
+}
+
+impl<Id> Constraints<Id> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn include(mut self, nodes: impl IntoIterator<Item = Id>) -> Self {
+        self.must_include.extend(nodes);
+        self
+    }
+
+    pub fn exclude(mut self, nodes: impl IntoIterator<Item = Id>) -> Self {
+        self.must_exclude.extend(nodes);
+        self
+    }
+
+    pub fn initial_solution(mut self, clique: Vec<Id>) -> Self {
+        self.initial_solution = Some(clique);
+        self
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ConstraintError<Id = NodeIndex> {
+    #[error("required nodes cannot be in one clique: {0}")]
+    Infeasible(VerifyError<Id>),
+    #[error("node {0:?} is not in the graph")]
+    NodeOutOfRange(Id),
+    #[error("node {0:?} is both required and excluded")]
+    Conflict(Id),
+    #[error("initial solution is not a clique: {0}")]
+    InvalidInitialSolution(VerifyError<Id>),
+    #[error("initial solution violates the constraints at node {0:?}")]
+    InitialSolutionViolates(Id),
+}
+
+pub fn find_max_clique_with<G: CliqueGraph>(
+    graph: G,
+    constraints: &Constraints<G::NodeId>,
+) -> Result<Vec<G::NodeId>, ConstraintError<G::NodeId>> {
+    find_max_clique_with_by(graph, constraints, Algorithm::Auto)
+}
+
+// Bron-Kerbosch 与遗传算法直接在原图上带约束搜索；
+// 其它算法（以及 Auto）在候选节点的诱导子图上求解，再加上必选节点
+pub fn find_max_clique_with_by<G: CliqueGraph>(
+    graph: G,
+    constraints: &Constraints<G::NodeId>,
+    algorithm: Algorithm,
+) -> Result<Vec<G::NodeId>, ConstraintError<G::NodeId>> {
+    check_clique(graph, &constraints.must_include).map_err(ConstraintError::Infeasible)?;
+    let compact = Compact::new(graph);
+    let adjacency = &compact.adjacency;
+    // check_clique 已保证必选节点都在图中
+    let required: Vec<usize> = constraints
+        .must_include
+        .iter()
+        .map(|&u| compact.index(u).unwrap())
+        .collect();
+    let allowed = allowed_nodes(&compact, &required, constraints)?;
+    let initial: Vec<usize> = match &constraints.initial_solution {
+        Some(clique) => check_initial(graph, &compact, constraints, clique, &allowed)?,
+        None => Vec::new(),
+    };
+    if required.is_empty() && allowed.is_clear() {
+        return Ok(Vec::new());
+    }
+
+    let clique = match algorithm {
+        Algorithm::BronKerbosch => {
+            find_max_clique_with_bk_restricted(adjacency, &required, &allowed, &initial)
+        }
+        Algorithm::Genetic => {
+            let restriction = Restriction {
+                required: to_bitvec(&required, adjacency.len()),
+                allowed: to_bitvec(&allowed.ones().collect::<Vec<_>>(), adjacency.len()),
+            };
+            find_max_clique_with_ga_restricted(
+                adjacency,
+                &GAConfig::default(),
+                &restriction,
+                &initial,
+            )
+        }
+        algorithm => {
+            let original: Vec<usize> = allowed.ones().collect();
+            let subgraph = induced_adjacency(adjacency, &original);
+            // 初始解去掉必选节点后是子图中的团，作为子图求解的下界
+            let warm_start = initial
+                .iter()
+                .filter_map(|u| original.binary_search(u).ok())
+                .collect();
+            let solution = solve_compact_from(
+                &subgraph,
+                &SolverOptions::new().algorithm(algorithm),
+                warm_start,
+            );
+            let mut clique = required.clone();
+            clique.extend(solution.clique.iter().map(|&u| original[u]));
+            clique
+        }
+    };
+    if clique.len() < initial.len() {
+        return Ok(compact.ids(initial));
+    }
+    Ok(compact.ids(clique))
+}
+
+// 检查 must_exclude，返回可以加入团的节点：
+// 与所有必选节点相邻、既不是必选节点也没有被排除
+fn allowed_nodes<G: CliqueGraph>(
+    compact: &Compact<G>,
+    required: &[usize],
+    constraints: &Constraints<G::NodeId>,
+) -> Result<FixedBitSet, ConstraintError<G::NodeId>> {
+    let node_count = compact.adjacency.len();
+    let mut allowed = FixedBitSet::with_capacity(node_count);
+    allowed.insert_range(..);
+    for &u in required {
+        let mut neighbors = FixedBitSet::with_capacity(node_count);
+        neighbors.extend(compact.adjacency[u].iter().copied());
+        allowed.intersect_with(&neighbors);
+    }
+    for &u in required {
+        allowed.remove(u);
+    }
+    for &u in &constraints.must_exclude {
+        let Some(index) = compact.index(u) else {
+            return Err(ConstraintError::NodeOutOfRange(u));
+        };
+        if constraints.must_include.contains(&u) {
+            return Err(ConstraintError::Conflict(u));
+        }
+        allowed.remove(index);
+    }
+    Ok(allowed)
+}
+
+fn check_initial<G: CliqueGraph>(
+    graph: G,
+    compact: &Compact<G>,
+    constraints: &Constraints<G::NodeId>,
+    clique: &[G::NodeId],
+    allowed: &FixedBitSet,
+) -> Result<Vec<usize>, ConstraintError<G::NodeId>> {
+    check_clique(graph, clique).map_err(ConstraintError::InvalidInitialSolution)?;
+    if let Some(&u) = constraints
+        .must_include
+        .iter()
+        .find(|u| !clique.contains(u))
+    {
+        return Err(ConstraintError::InitialSolutionViolates(u));
+    }
+    let initial: Vec<usize> = clique.iter().map(|&u| compact.index(u).unwrap()).collect();
+    if let Some((&u, _)) = clique
+        .iter()
+        .zip(&initial)
+        .find(|&(u, &index)| !allowed.contains(index) && !constraints.must_include.contains(u))
+    {
+        return Err(ConstraintError::InitialSolutionViolates(u));
+    }
+    Ok(initial)
+}
+
+fn to_bitvec(nodes: &[usize], node_count: usize) -> BitVec {
+    let mut bits = bitvec![0; node_count];
+    for &u in nodes {
+        bits.set(u, true);
+    }
+    bits
+}
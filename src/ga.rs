@@ -1,18 +1,21 @@
 use bitvec::prelude::*;
-use petgraph::{
-    graph::{NodeIndex, UnGraph},
-    visit::EdgeRef,
-};
 use rand::prelude::*;
 use rayon::prelude::*;
-use std::ops::Not;
+use std::{collections::HashSet, ops::Not};
 
-#[derive(Clone)]
-struct GAConfig {
+use crate::graph::{CliqueGraph, Compact};
+use crate::solver::Stop;
+
+// 遗传算法参数，通过链式调用设置：
+// GAConfig::new().population_size(30).max_generations(500).seed(42)
+// 设置种子后，同一张图上的运行结果逐位可复现（与线程数无关）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GAConfig {
     population_size: usize,
     max_generations: usize,
     shuffle_tolerance: usize,
     local_improvement_iter: usize,
+    seed: Option<u64>,
 }
 
 impl Default for GAConfig {
@@ -22,57 +25,271 @@ impl Default for GAConfig {
             max_generations: 300,
             shuffle_tolerance: 10,
             local_improvement_iter: 10,
+            seed: None,
         }
     }
 }
 
-pub fn find_max_cliques_with_ga(graph: &UnGraph<(), ()>) -> Vec<NodeIndex> {
-    // 构建邻接矩阵
-    let node_count = graph.node_count();
-    let mut adj_matrix = vec![bitvec![0;node_count]; node_count];
-    for edge in graph.edge_references() {
-        let (a, b) = (edge.source().index(), edge.target().index());
-        adj_matrix[a].set(b, true);
-        adj_matrix[b].set(a, true);
+impl GAConfig {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    // 遗传算法的具体实现
-    let config = GAConfig::default();
-    let mut ga = GeneticAlgorithm::new(&adj_matrix, config);
-    for _ in 0..ga.config.max_generations {
-        ga.evolve();
+    // 种群至少需要两个个体才能交叉
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        self.population_size = population_size.max(2);
+        self
+    }
+
+    pub fn max_generations(mut self, max_generations: usize) -> Self {
+        self.max_generations = max_generations;
+        self
+    }
+
+    // 最优解连续多少代没有刷新就重新生成种群
+    pub fn shuffle_tolerance(mut self, shuffle_tolerance: usize) -> Self {
+        self.shuffle_tolerance = shuffle_tolerance;
+        self
     }
 
+    pub fn local_improvement_iter(mut self, local_improvement_iter: usize) -> Self {
+        self.local_improvement_iter = local_improvement_iter;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    // 没有指定种子时从系统随机源取一个
+    fn make_rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        }
+    }
+}
+
+pub fn find_max_cliques_with_ga<G: CliqueGraph>(graph: G) -> Vec<G::NodeId> {
+    find_max_cliques_with_ga_config(graph, &GAConfig::default())
+}
+
+pub fn find_max_cliques_with_ga_config<G: CliqueGraph>(
+    graph: G,
+    config: &GAConfig,
+) -> Vec<G::NodeId> {
+    let compact = Compact::new(graph);
+    compact.ids(find_max_cliques_with_ga_until(
+        &compact.adjacency,
+        config,
+        &Stop::never(),
+    ))
+}
+
+// 每一代结束后回调 observer，可用于绘制收敛曲线或推送进度
+pub fn find_max_cliques_with_ga_observed<G: CliqueGraph>(
+    graph: G,
+    config: &GAConfig,
+    observer: &mut impl GAObserver,
+) -> Vec<G::NodeId> {
+    let compact = Compact::new(graph);
+    compact.ids(find_max_cliques_with_ga_observed_until(
+        &compact.adjacency,
+        config,
+        &Stop::never(),
+        observer,
+    ))
+}
+
+// 每代开始前检查是否需要停止，停止时返回目前为止的最优解
+pub(crate) fn find_max_cliques_with_ga_until(
+    adjacency: &[Vec<usize>],
+    config: &GAConfig,
+    stop: &Stop,
+) -> Vec<usize> {
+    find_max_cliques_with_ga_observed_until(adjacency, config, stop, &mut |_: &GenerationStats| {})
+}
+
+pub(crate) fn find_max_cliques_with_ga_observed_until(
+    adjacency: &[Vec<usize>],
+    config: &GAConfig,
+    stop: &Stop,
+    observer: &mut impl GAObserver,
+) -> Vec<usize> {
+    // 初始种群至少需要一个节点
+    if adjacency.is_empty() {
+        return Vec::new();
+    }
+    let adj_matrix = build_adj_matrix(adjacency);
+
+    // 遗传算法的具体实现
+    let mut ga = GeneticAlgorithm::new(&adj_matrix, config.clone());
+    ga.run_observed(stop, observer);
+
     // 映射回去
-    ga.best_clique().iter_ones().map(NodeIndex::new).collect()
+    ga.best_clique().iter_ones().collect()
+}
+
+// 带约束的遗传算法，initial 为已知满足约束的团（原图编号）
+pub(crate) fn find_max_clique_with_ga_restricted(
+    adjacency: &[Vec<usize>],
+    config: &GAConfig,
+    restriction: &Restriction,
+    initial: &[usize],
+) -> Vec<usize> {
+    if adjacency.is_empty() {
+        return initial.to_vec();
+    }
+    let adj_matrix = build_adj_matrix(adjacency);
+    let mut ga = GeneticAlgorithm::with_restriction(
+        &adj_matrix,
+        config.clone(),
+        Cardinality,
+        Some(restriction),
+    );
+    ga.seed_clique(initial);
+    ga.run();
+    ga.best_clique().iter_ones().collect()
+}
+
+// 返回种群发现过的所有规模最大且互不相同的团
+pub fn find_all_max_cliques_with_ga<G: CliqueGraph>(graph: G) -> Vec<Vec<G::NodeId>> {
+    let compact = Compact::new(graph);
+    all_max_cliques_with_ga(&compact.adjacency)
+        .into_iter()
+        .map(|clique| compact.ids(clique))
+        .collect()
+}
+
+pub(crate) fn all_max_cliques_with_ga(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    if adjacency.is_empty() {
+        return Vec::new();
+    }
+    let adj_matrix = build_adj_matrix(adjacency);
+    let mut ga = GeneticAlgorithm::new(&adj_matrix, GAConfig::default());
+    ga.record_discovered();
+    ga.run();
+
+    let ranked = ga.ranked_discovered();
+    let best = ranked.first().map_or(0, |c| c.count_ones());
+    ranked
+        .into_iter()
+        .take_while(|c| c.count_ones() == best)
+        .map(|c| c.iter_ones().collect())
+        .collect()
+}
+
+// 返回种群发现过的前 k 大且互不相同的团
+pub fn find_top_k_cliques_with_ga<G: CliqueGraph>(graph: G, k: usize) -> Vec<Vec<G::NodeId>> {
+    let compact = Compact::new(graph);
+    top_k_cliques_with_ga(&compact.adjacency, k)
+        .into_iter()
+        .map(|clique| compact.ids(clique))
+        .collect()
+}
+
+pub(crate) fn top_k_cliques_with_ga(adjacency: &[Vec<usize>], k: usize) -> Vec<Vec<usize>> {
+    if adjacency.is_empty() {
+        return Vec::new();
+    }
+    let adj_matrix = build_adj_matrix(adjacency);
+    let mut ga = GeneticAlgorithm::new(&adj_matrix, GAConfig::default());
+    ga.record_discovered();
+    ga.run();
+
+    ga.ranked_discovered()
+        .into_iter()
+        .take(k)
+        .map(|c| c.iter_ones().collect())
+        .collect()
+}
+
+// 构建邻接矩阵
+pub(crate) fn build_adj_matrix(adjacency: &[Vec<usize>]) -> Vec<BitVec> {
+    let node_count = adjacency.len();
+    let mut adj_matrix = vec![bitvec![0;node_count]; node_count];
+    for (row, neighbors) in adj_matrix.iter_mut().zip(adjacency) {
+        for &v in neighbors {
+            row.set(v, true);
+        }
+    }
+    adj_matrix
+}
+
+// 个体的适应度，默认是团的节点数
+pub(crate) trait Fitness: Sync {
+    type Value: PartialOrd + Copy + Send + Sync;
+
+    fn fitness(&self, clique: &BitSlice) -> Self::Value;
+}
+
+pub(crate) struct Cardinality;
+
+impl Fitness for Cardinality {
+    type Value = usize;
+
+    fn fitness(&self, clique: &BitSlice) -> usize {
+        clique.count_ones()
+    }
+}
+
+// 按适应度比较，不可比较（NaN）时视为相等
+fn fitter<F: Fitness>(fitness: &F, a: &BitSlice, b: &BitSlice) -> std::cmp::Ordering {
+    fitness
+        .fitness(a)
+        .partial_cmp(&fitness.fitness(b))
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+// 约束：required 中的节点必须在团内，其余节点只能从 allowed 中选取
+// allowed 中不含 required 本身、被排除的节点以及与 required 不全相邻的节点
+pub(crate) struct Restriction {
+    pub(crate) required: BitVec,
+    pub(crate) allowed: BitVec,
 }
 
 struct Clique<'a> {
     clique: BitVec, // 已有最大团
     pa: BitVec,     // 候选集 Possible Additions
     adj_matrix: &'a [BitVec],
+    restriction: Option<&'a Restriction>,
     node_count: usize,
 }
 
 impl<'a> Clique<'a> {
-    fn new(adj_matrix: &'a [BitVec], start: usize) -> Self {
+    fn new(adj_matrix: &'a [BitVec], restriction: Option<&'a Restriction>, start: usize) -> Self {
         let node_count = adj_matrix.len();
         let mut clique = bitvec![0;node_count];
         clique.set(start, true);
         let mut pa = adj_matrix[start].clone();
         pa.set(start, false);
+        // start 取自 allowed 或 required，与所有必选节点相邻
+        if let Some(restriction) = restriction {
+            clique |= &restriction.required;
+            pa &= &restriction.allowed;
+        }
         Clique {
             clique,
             pa,
             adj_matrix,
+            restriction,
             node_count,
         }
     }
 
+    // 可以被移除的节点（必选节点除外）
+    fn removable(&self) -> Vec<usize> {
+        self.clique
+            .iter_ones()
+            .filter(|&node| !self.restriction.is_some_and(|r| r.required[node]))
+            .collect()
+    }
+
     // 添加一个节点到最大团中
     // 注意！！这里不检查加入是否合法！！！
     fn add_vertex(&mut self, node: usize) {
-        if self.clique[node] == false {
+        if !self.clique[node] {
             self.clique.set(node, true);
             self.pa &= &self.adj_matrix[node];
             self.pa.set(node, false);
@@ -82,7 +299,7 @@ impl<'a> Clique<'a> {
     // 从最大团中移除一个节点
     // 复杂度较高，需要重新计算一次产生新的pa
     fn remove_vertex(&mut self, node: usize) {
-        if !self.clique[node] {
+        if !self.clique[node] || self.restriction.is_some_and(|r| r.required[node]) {
             return;
         }
 
@@ -100,6 +317,9 @@ impl<'a> Clique<'a> {
         for clique_node in remaining_clique.iter_ones() {
             self.pa &= &self.adj_matrix[clique_node];
         }
+        if let Some(restriction) = self.restriction {
+            self.pa &= &restriction.allowed;
+        }
 
         // 更新团状态
         self.clique = remaining_clique;
@@ -111,11 +331,11 @@ impl<'a> Clique<'a> {
     }
 
     // 对当前最大团进行局部改进（随机移除两个节点，然后按度数排序重新加入）
-    fn local_improvement(&mut self, iteration: usize, rng: &mut impl Rng) {
+    fn local_improvement(&mut self, iteration: usize, rng: &mut impl Rng, fitness: &impl Fitness) {
         let mut best = self.clone();
         for _ in 0..iteration {
             let mut temp = self.clone();
-            let nodes: Vec<_> = temp.clique.iter_ones().collect();
+            let nodes = temp.removable();
 
             if nodes.len() > 1 {
                 let (n1, n2) = pick_two(&nodes, rng);
@@ -124,7 +344,7 @@ impl<'a> Clique<'a> {
                 temp.greedy_expand_in_pa();
             }
 
-            if temp.clique.count_ones() > best.clique.count_ones() {
+            if fitter(fitness, &temp.clique, &best.clique).is_gt() {
                 best = temp;
             }
         }
@@ -162,49 +382,103 @@ impl Clone for Clique<'_> {
             clique: self.clique.clone(),
             pa: self.pa.clone(),
             adj_matrix: self.adj_matrix, // is ref
+            restriction: self.restriction,
             node_count: self.node_count,
         }
     }
 }
 
-struct GeneticAlgorithm<'a> {
+// 一代进化结束后的统计信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationStats {
+    // 从 1 开始计数
+    pub generation: usize,
+    // 到目前为止找到的最优团的规模
+    pub best_size: usize,
+    // 新一代种群中每个个体的团规模
+    pub population_sizes: Vec<usize>,
+    pub stagnation_counter: usize,
+    // 本代开始时是否因停滞而重新生成了种群
+    pub reshuffled: bool,
+}
+
+// 遗传算法的观察者，闭包 FnMut(&GenerationStats) 可以直接使用
+pub trait GAObserver {
+    fn on_generation(&mut self, stats: &GenerationStats);
+}
+
+impl<T: FnMut(&GenerationStats)> GAObserver for T {
+    fn on_generation(&mut self, stats: &GenerationStats) {
+        self(stats)
+    }
+}
+
+pub(crate) struct GeneticAlgorithm<'a, F: Fitness = Cardinality> {
     population: Vec<Clique<'a>>,
     adj_matrix: &'a [BitVec],
+    restriction: Option<&'a Restriction>,
+    // 可以作为初始节点的节点
+    starts: Vec<usize>,
     config: GAConfig,
+    fitness: F,
+    rng: StdRng,
     best_clique: BitVec,
+    // 种群中出现过的所有互不相同的团，只在 record_discovered 之后记录
+    discovered: Option<HashSet<BitVec>>,
     stagnation_counter: usize,
-    prev_best_count: usize,
+    prev_best: Option<F::Value>,
 }
 
 impl<'a> GeneticAlgorithm<'a> {
-    fn new(adj_matrix: &'a [BitVec], config: GAConfig) -> Self {
+    pub(crate) fn new(adj_matrix: &'a [BitVec], config: GAConfig) -> Self {
+        Self::with_fitness(adj_matrix, config, Cardinality)
+    }
+}
+
+impl<'a, F: Fitness> GeneticAlgorithm<'a, F> {
+    pub(crate) fn with_fitness(adj_matrix: &'a [BitVec], config: GAConfig, fitness: F) -> Self {
+        Self::with_restriction(adj_matrix, config, fitness, None)
+    }
+
+    // 带约束时所有个体都包含必选节点，只在 allowed 中扩展
+    pub(crate) fn with_restriction(
+        adj_matrix: &'a [BitVec],
+        config: GAConfig,
+        fitness: F,
+        restriction: Option<&'a Restriction>,
+    ) -> Self {
         let node_count = adj_matrix.len();
-        let mut rng = rand::rng();
+        let mut rng = config.make_rng();
         let mut population = Vec::with_capacity(config.population_size);
 
+        // allowed 为空时必选节点本身就是唯一的解，只能从必选节点出发
+        let starts: Vec<usize> = match restriction {
+            None => (0..node_count).collect(),
+            Some(r) if r.allowed.any() => r.allowed.iter_ones().collect(),
+            Some(r) => r.required.iter_ones().collect(),
+        };
+
         // 初始种群生成
         // 随机生成贪心极大团个体
-        let starts = (0..node_count).choose_multiple(&mut rng, config.population_size - 1);
-        for start in starts {
-            let mut clique = Clique::new(adj_matrix, start);
+        for &start in starts.choose_multiple(&mut rng, config.population_size - 1) {
+            let mut clique = Clique::new(adj_matrix, restriction, start);
             clique.greedy_expand_in_pa();
             population.push(clique);
         }
 
         // 添加度数最高节点的个体（天选之子）
-        let max_degree_node = adj_matrix
+        let max_degree_node = starts
             .iter()
-            .enumerate()
-            .max_by_key(|(_, vec)| vec.count_ones())
-            .map(|(i, _)| i)
+            .copied()
+            .max_by_key(|&i| adj_matrix[i].count_ones())
             .unwrap();
 
-        let mut nb_clique = Clique::new(adj_matrix, max_degree_node);
+        let mut nb_clique = Clique::new(adj_matrix, restriction, max_degree_node);
         nb_clique.greedy_expand_in_pa();
         population.push(nb_clique);
         let best_clique = population
             .iter()
-            .max_by_key(|p| p.clique.count_ones())
+            .max_by(|a, b| fitter(&fitness, &a.clique, &b.clique))
             .unwrap()
             .clone()
             .clique;
@@ -212,85 +486,171 @@ impl<'a> GeneticAlgorithm<'a> {
         GeneticAlgorithm {
             population,
             adj_matrix,
+            restriction,
+            starts,
             config,
+            fitness,
+            rng,
+            discovered: None,
             best_clique,
             stagnation_counter: 0,
-            prev_best_count: 0,
+            prev_best: None,
+        }
+    }
+
+    pub(crate) fn run(&mut self) {
+        self.run_until(&Stop::never());
+    }
+
+    pub(crate) fn run_until(&mut self, stop: &Stop) {
+        self.run_observed(stop, &mut |_: &GenerationStats| {});
+    }
+
+    pub(crate) fn run_observed(&mut self, stop: &Stop, observer: &mut impl GAObserver) {
+        self.record_population();
+        for generation in 1..=self.config.max_generations {
+            if stop.should_stop() {
+                break;
+            }
+            let reshuffled = self.evolve();
+            self.record_population();
+            // 及时更新最优解，避免停滞洗牌时丢掉新一代中更好的个体
+            self.refresh_best();
+            stop.improved(self.best_clique.count_ones(), self.best_clique.iter_ones());
+            observer.on_generation(&self.stats(generation, reshuffled));
         }
     }
-    
+
+    fn stats(&self, generation: usize, reshuffled: bool) -> GenerationStats {
+        GenerationStats {
+            generation,
+            best_size: self.best_clique.count_ones(),
+            population_sizes: self
+                .population
+                .iter()
+                .map(|p| p.clique.count_ones())
+                .collect(),
+            stagnation_counter: self.stagnation_counter,
+            reshuffled,
+        }
+    }
+
+    // 用当前种群中的最优个体更新最优解
+    fn refresh_best(&mut self) {
+        if let Some(p) = self
+            .population
+            .iter()
+            .max_by(|a, b| fitter(&self.fitness, &a.clique, &b.clique))
+            && fitter(&self.fitness, &p.clique, &self.best_clique).is_gt()
+        {
+            self.best_clique = p.clique.clone();
+        }
+    }
+
+    // 之后每一代都记录种群中的团，供 ranked_discovered 使用
+    pub(crate) fn record_discovered(&mut self) {
+        self.discovered.get_or_insert_with(HashSet::new);
+    }
+
+    // 记录当前种群中的团
+    fn record_population(&mut self) {
+        let Some(discovered) = &mut self.discovered else {
+            return;
+        };
+        for p in &self.population {
+            if !discovered.contains(&p.clique) {
+                discovered.insert(p.clique.clone());
+            }
+        }
+    }
+
+    // 按适应度降序排列发现过的团，相同时按节点编号排序以保证结果稳定
+    pub(crate) fn ranked_discovered(&self) -> Vec<&BitVec> {
+        let mut ranked: Vec<_> = self.discovered.iter().flatten().collect();
+        ranked.sort_by(|a, b| {
+            fitter(&self.fitness, b, a).then_with(|| a.iter_ones().cmp(b.iter_ones()))
+        });
+        ranked
+    }
+
     fn generate_random_population(&mut self) {
         self.population.clear();
-        let starts = (0..self.adj_matrix.len()).choose_multiple(&mut rand::rng(), self.config.population_size - 1);
+        let starts: Vec<usize> = self
+            .starts
+            .choose_multiple(&mut self.rng, self.config.population_size - 1)
+            .copied()
+            .collect();
         for start in starts {
-            let mut clique = Clique::new(self.adj_matrix, start);
+            let mut clique = Clique::new(self.adj_matrix, self.restriction, start);
             clique.greedy_expand_in_pa();
             self.population.push(clique);
         }
     }
 
     // main function
-    fn evolve(&mut self) {
+    // 返回本代是否重新洗牌
+    fn evolve(&mut self) -> bool {
         // 停滞处理
-        if self.prev_best_count == self.best_clique.count_ones() {
+        let mut reshuffled = false;
+        let best = self.fitness.fitness(&self.best_clique);
+        if self.prev_best == Some(best) {
             self.stagnation_counter += 1;
             if self.stagnation_counter >= self.config.shuffle_tolerance {
                 // 重新洗牌
                 self.generate_random_population();
                 self.stagnation_counter = 0;
+                reshuffled = true;
             }
         } else {
-            self.prev_best_count = self.best_clique.count_ones();
+            self.prev_best = Some(best);
             self.stagnation_counter = 0;
         }
         // 存储当前最优解
         let mut local_best = self
             .population
             .iter()
-            .max_by_key(|p| p.clique.count_ones())
+            .max_by(|a, b| fitter(&self.fitness, &a.clique, &b.clique))
             .unwrap()
             .clone();
-        if local_best.clique.count_ones() > self.best_clique.count_ones() {
-            // println!("New best: {}", local_best.clique.count_ones());
+        if fitter(&self.fitness, &local_best.clique, &self.best_clique).is_gt() {
             self.best_clique = local_best.clique.clone();
         }
 
         // 精英保存
-        local_best.local_improvement(self.config.local_improvement_iter, &mut rand::rng());
+        local_best.local_improvement(
+            self.config.local_improvement_iter,
+            &mut self.rng,
+            &self.fitness,
+        );
         self.population.push(local_best);
-        
-        // dbg
-        // for p in &self.population {
-        //     print!("{} ", p.clique.count_ones());
-        // }
-        // println!();
-        // pause to debug
-        // std::thread::sleep(std::time::Duration::from_secs(1));
 
         let mut new_population = Vec::with_capacity(self.config.population_size);
 
         // 生成后代 多线程优化
-        let offspring: Vec<_> = (0..(self.config.population_size - 1))
+        // 每个后代的随机数种子由主随机数生成器顺序产生，结果与线程调度无关
+        let seeds: Vec<u64> = (0..(self.config.population_size - 1))
+            .map(|_| self.rng.random())
+            .collect();
+        let offspring: Vec<_> = seeds
             .into_par_iter()
-            .map_init(
-                || rand::rng(),
-                |rng, _| {
-                    let (p1, p2) = pick_two(&self.population, rng);
-                    let mut child = self.crossover(p1, p2, rng);
-
-                    if child.clique.count_ones() <= p1.clique.count_ones()
-                        || child.clique.count_ones() <= p2.clique.count_ones()
-                    {
-                        self.mutate(&mut child, rng);
-                    }
-
-                    child.local_improvement(self.config.local_improvement_iter, rng);
-                    child
-                },
-            )
+            .map(|seed| {
+                let rng = &mut StdRng::seed_from_u64(seed);
+                let (p1, p2) = pick_two(&self.population, rng);
+                let mut child = self.crossover(p1, p2, rng);
+
+                if fitter(&self.fitness, &child.clique, &p1.clique).is_le()
+                    || fitter(&self.fitness, &child.clique, &p2.clique).is_le()
+                {
+                    self.mutate(&mut child, rng);
+                }
+
+                child.local_improvement(self.config.local_improvement_iter, rng, &self.fitness);
+                child
+            })
             .collect();
         new_population.extend(offspring);
         self.population = new_population;
+        reshuffled
     }
 
     fn crossover(&self, p1: &Clique, p2: &Clique, rng: &mut impl Rng) -> Clique<'a> {
@@ -300,13 +660,14 @@ impl<'a> GeneticAlgorithm<'a> {
             // 随机化会不会好一点?
             let mut child = Clique::new(
                 self.adj_matrix,
+                self.restriction,
                 common_nodes.iter_ones().choose(rng).unwrap(),
             );
             for node in common_nodes
                 .iter_ones()
                 .choose_multiple(rng, common_nodes.count_ones())
             {
-                if child.pa[node] == true {
+                if child.pa[node] {
                     child.add_vertex(node); // 两个团的交一定是团
                 }
             }
@@ -325,7 +686,7 @@ impl<'a> GeneticAlgorithm<'a> {
             .collect();
         sorted_nodes.sort_unstable_by_key(|&(_, deg)| std::cmp::Reverse(deg));
 
-        let mut child = Clique::new(self.adj_matrix, sorted_nodes[0].0);
+        let mut child = Clique::new(self.adj_matrix, self.restriction, sorted_nodes[0].0);
         for (node, _) in &sorted_nodes[1..] {
             if child.pa[*node] {
                 child.add_vertex(*node);
@@ -336,12 +697,12 @@ impl<'a> GeneticAlgorithm<'a> {
     }
 
     fn mutate(&self, clique: &mut Clique, rng: &mut impl Rng) {
-        if clique.clique.not_any() {
+        let nodes = clique.removable();
+        if nodes.is_empty() {
             return;
         }
 
         // 删掉一个先（这里本来是有一个变异数的，不过取 1了就简化了）
-        let nodes: Vec<_> = clique.clique.iter_ones().collect();
         let idx = rng.random_range(0..nodes.len());
         clique.remove_vertex(nodes[idx]);
 
@@ -362,7 +723,23 @@ impl<'a> GeneticAlgorithm<'a> {
         }
     }
 
-    fn best_clique(&mut self) -> &BitVec {
+    // 把已知的团（例如用户给出的初始解）加入种群
+    pub(crate) fn seed_clique(&mut self, nodes: &[usize]) {
+        let Some((&first, rest)) = nodes.split_first() else {
+            return;
+        };
+        let mut clique = Clique::new(self.adj_matrix, self.restriction, first);
+        for &node in rest {
+            clique.add_vertex(node);
+        }
+        clique.greedy_expand_in_pa();
+        if fitter(&self.fitness, &clique.clique, &self.best_clique).is_gt() {
+            self.best_clique = clique.clique.clone();
+        }
+        self.population.push(clique);
+    }
+
+    pub(crate) fn best_clique(&mut self) -> &BitVec {
         &self.best_clique
     }
 }
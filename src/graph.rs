@@ -0,0 +1,110 @@
+use fixedbitset::FixedBitSet;
+use petgraph::visit::{EdgeCount, IntoNeighbors, IntoNodeIdentifiers, NodeCount, NodeIndexable};
+
+// 求解器接受的图：UnGraph、StableGraph、GraphMap、Csr 等的引用都满足，节点和边的权重任意
+// 有向图按无向图处理，自环与重复边会被忽略
+pub trait CliqueGraph:
+    IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + NodeCount + EdgeCount
+{
+}
+
+impl<G> CliqueGraph for G where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + NodeCount + EdgeCount
+{
+}
+
+// 求解器内部使用的压缩表示：节点按 node_identifiers 的顺序编号为 0..n，
+// 邻接表有序、去重且不含自环。StableGraph 删除节点留下的空位不会占用编号
+pub(crate) struct Compact<G: CliqueGraph> {
+    graph: G,
+    ids: Vec<G::NodeId>,
+    // graph.to_index(id) -> 内部编号，空位为 usize::MAX
+    position: Vec<usize>,
+    pub(crate) adjacency: Vec<Vec<usize>>,
+}
+
+impl<G: CliqueGraph> Compact<G> {
+    pub(crate) fn new(graph: G) -> Self {
+        let ids: Vec<G::NodeId> = graph.node_identifiers().collect();
+        let mut position = vec![usize::MAX; graph.node_bound()];
+        for (i, &id) in ids.iter().enumerate() {
+            position[graph.to_index(id)] = i;
+        }
+
+        // 两个方向都记录一次，有向图也得到对称的邻接表
+        let mut adjacency = vec![Vec::new(); ids.len()];
+        for (u, &id) in ids.iter().enumerate() {
+            for v in graph.neighbors(id) {
+                let v = position[graph.to_index(v)];
+                if v != u {
+                    adjacency[u].push(v);
+                    adjacency[v].push(u);
+                }
+            }
+        }
+        for neighbors in &mut adjacency {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+
+        Compact {
+            graph,
+            ids,
+            position,
+            adjacency,
+        }
+    }
+
+    // 内部编号 -> 调用方的节点 ID
+    pub(crate) fn id(&self, u: usize) -> G::NodeId {
+        self.ids[u]
+    }
+
+    pub(crate) fn ids(&self, nodes: impl IntoIterator<Item = usize>) -> Vec<G::NodeId> {
+        nodes.into_iter().map(|u| self.ids[u]).collect()
+    }
+
+    // 调用方的节点 ID -> 内部编号，不在图中时返回 None
+    pub(crate) fn index(&self, id: G::NodeId) -> Option<usize> {
+        self.position
+            .get(self.graph.to_index(id))
+            .copied()
+            .filter(|&u| u != usize::MAX)
+    }
+}
+
+pub(crate) fn edge_count(adjacency: &[Vec<usize>]) -> usize {
+    adjacency.iter().map(Vec::len).sum::<usize>() / 2
+}
+
+// nodes（升序）的诱导子图，子图中的节点 i 对应 nodes[i]
+pub(crate) fn induced_adjacency(adjacency: &[Vec<usize>], nodes: &[usize]) -> Vec<Vec<usize>> {
+    let mut new_index = vec![usize::MAX; adjacency.len()];
+    for (i, &v) in nodes.iter().enumerate() {
+        new_index[v] = i;
+    }
+    // 原编号有序，映射后的邻接表仍然有序
+    nodes
+        .iter()
+        .map(|&u| {
+            adjacency[u]
+                .iter()
+                .map(|&v| new_index[v])
+                .filter(|&v| v != usize::MAX)
+                .collect()
+        })
+        .collect()
+}
+
+// 位集形式的邻接表
+pub(crate) fn build_neighbors(adjacency: &[Vec<usize>]) -> Vec<FixedBitSet> {
+    let node_count = adjacency.len();
+    adjacency
+        .iter()
+        .map(|neighbors| {
+            let mut set = FixedBitSet::with_capacity(node_count);
+            set.extend(neighbors.iter().copied());
+            set
+        })
+        .collect()
+}
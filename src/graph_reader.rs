@@ -1,51 +1,1058 @@
-use petgraph::graph::UnGraph;
+use flate2::bufread::MultiGzDecoder;
+use petgraph::graph::{IndexType, NodeIndex, UnGraph};
+use quick_xml::events::Event;
 use std::{
+    collections::{HashMap, HashSet},
     fs::File,
-    io::{BufRead, BufReader},
+    io::{self, BufRead, BufReader, Read},
 };
 use thiserror::Error;
+use xz2::bufread::XzDecoder;
 
 #[derive(Error, Debug)]
 pub enum GraphErr {
-    #[error("invalid DIMACS format: {0}")]
-    ParseError(String),
+    #[error("invalid graph file at line {line}, column {column}: {kind}")]
+    ParseError {
+        line: usize,
+        column: usize,
+        kind: ParseErrorKind,
+    },
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
 
-impl From<&str> for GraphErr {
-    fn from(s: &str) -> Self {
-        GraphErr::ParseError(s.into())
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    #[error("edge before the problem line")]
+    MissingProblemLine,
+    #[error("more than one problem line")]
+    DuplicateProblemLine,
+    #[error("unsupported problem format `{0}`, expected `edge` or `col`")]
+    UnsupportedFormat(String),
+    #[error("expected {0}")]
+    MissingToken(&'static str),
+    #[error("unexpected token `{0}`")]
+    UnexpectedToken(String),
+    #[error("invalid number `{0}`")]
+    InvalidNumber(String),
+    #[error("too many vertices: {0}")]
+    TooManyVertices(usize),
+    #[error("vertex {vertex} out of range 1..={node_count}")]
+    VertexOutOfRange { vertex: usize, node_count: usize },
+    #[error("self-loop on vertex {0}")]
+    SelfLoop(usize),
+    #[error("duplicate edge {0} {1}")]
+    DuplicateEdge(usize, usize),
+    #[error("binary adjacency matrix ends at row {0}")]
+    TruncatedBitmap(usize),
+    #[error("declared {declared} edges but found {actual}")]
+    EdgeCountMismatch { declared: usize, actual: usize },
+    #[error("declared {declared} vertices but found {actual}")]
+    NodeCountMismatch { declared: usize, actual: usize },
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("unknown node `{0}`")]
+    UnknownNode(String),
+    #[error("XML error: {0}")]
+    Xml(String),
+}
+
+// p 行声明的节点会全部预先创建，几十个字节的输入就能声明数十亿个节点，
+// 声明的节点数超过上限时报错而不是尝试分配
+pub const DEFAULT_MAX_VERTICES: usize = 1 << 25;
+
+// 读取选项，默认拒绝自环和重复边；真实数据的 p 行边数常常不准，默认不校验
+#[derive(Debug, Clone)]
+pub struct DimacsOptions {
+    dedup: bool,
+    skip_edge_count_check: bool,
+    max_vertices: usize,
+}
+
+impl Default for DimacsOptions {
+    fn default() -> Self {
+        DimacsOptions {
+            dedup: false,
+            skip_edge_count_check: true,
+            max_vertices: DEFAULT_MAX_VERTICES,
+        }
+    }
+}
+
+impl DimacsOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 允许声明的最大节点数，默认为 DEFAULT_MAX_VERTICES
+    pub fn max_vertices(mut self, max_vertices: usize) -> Self {
+        self.max_vertices = max_vertices;
+        self
+    }
+
+    // 忽略自环和重复边，而不是报错
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    // 是否跳过声明边数的校验，默认跳过；传入 false 启用严格校验
+    pub fn skip_edge_count_check(mut self, skip: bool) -> Self {
+        self.skip_edge_count_check = skip;
+        self
     }
 }
 
 pub fn read_dimacs(path: &str) -> Result<UnGraph<(), ()>, GraphErr> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    let mut graph = UnGraph::new_undirected();
-    let mut nodes = Vec::new();
+    read_dimacs_with(path, &DimacsOptions::default())
+}
+
+pub fn read_dimacs_with(path: &str, options: &DimacsOptions) -> Result<UnGraph<(), ()>, GraphErr> {
+    read_dimacs_from_with(BufReader::new(File::open(path)?), options)
+}
+
+pub fn read_dimacs_binary(path: &str) -> Result<UnGraph<(), ()>, GraphErr> {
+    read_dimacs_binary_from(BufReader::new(File::open(path)?))
+}
+
+// 从任意输入读取（标准输入、HTTP 请求体、内存缓冲区等），压缩输入会自动解压
+pub fn read_dimacs_from<R: BufRead>(reader: R) -> Result<UnGraph<(), ()>, GraphErr> {
+    read_dimacs_from_with(reader, &DimacsOptions::default())
+}
+
+pub fn read_dimacs_from_with<R: BufRead>(
+    reader: R,
+    options: &DimacsOptions,
+) -> Result<UnGraph<(), ()>, GraphErr> {
+    read_dimacs_file_from(reader, options).map(|file| file.graph)
+}
+
+pub fn read_dimacs_binary_from<R: BufRead>(reader: R) -> Result<UnGraph<(), ()>, GraphErr> {
+    parse_dimacs_binary(decompress(reader)?, &DimacsOptions::default()).map(|file| file.graph)
+}
+
+// 图连同文件中的注释行，写回时可以保留注释（见 graph_writer::write_dimacs）
+#[derive(Debug, Clone)]
+pub struct DimacsFile {
+    pub graph: UnGraph<(), ()>,
+    // 去掉开头 `c ` 的注释内容
+    pub comments: Vec<String>,
+}
+
+pub fn read_dimacs_file(path: &str, options: &DimacsOptions) -> Result<DimacsFile, GraphErr> {
+    read_dimacs_file_from(BufReader::new(File::open(path)?), options)
+}
+
+// 根据开头自动区分 ASCII 与二进制格式（二进制格式以十进制的头部长度开头）
+pub fn read_dimacs_file_from<R: BufRead>(
+    reader: R,
+    options: &DimacsOptions,
+) -> Result<DimacsFile, GraphErr> {
+    let mut reader = decompress(reader)?;
+    skip_bom(&mut reader)?;
+    if reader.fill_buf()?.first().is_some_and(u8::is_ascii_digit) {
+        parse_dimacs_binary(reader, options)
+    } else {
+        parse_dimacs(reader, options)
+    }
+}
+
+// 根据魔数识别 gzip / xz / zstd 压缩并透明解压，其它输入原样返回
+pub fn decompress<'r, R: BufRead + 'r>(mut reader: R) -> io::Result<Box<dyn BufRead + 'r>> {
+    const GZIP: &[u8] = &[0x1f, 0x8b];
+    const XZ: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+    const ZSTD: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+    let head = reader.fill_buf()?;
+    Ok(if head.starts_with(GZIP) {
+        Box::new(BufReader::new(MultiGzDecoder::new(reader)))
+    } else if head.starts_with(XZ) {
+        Box::new(BufReader::new(XzDecoder::new_multi_decoder(reader)))
+    } else if head.starts_with(ZSTD) {
+        Box::new(BufReader::new(zstd::Decoder::with_buffer(reader)?))
+    } else {
+        Box::new(reader)
+    })
+}
+
+// Windows 下的编辑器常在文本开头写入 UTF-8 BOM，所有格式在解析前统一跳过
+fn skip_bom(reader: &mut impl BufRead) -> io::Result<()> {
+    const BOM: &[u8] = "\u{feff}".as_bytes();
+    if reader.fill_buf()?.starts_with(BOM) {
+        reader.consume(BOM.len());
+    }
+    Ok(())
+}
+
+// 支持 `p edge` 与 `p col`，顶点编号从 1 开始，`c` 开头为注释
+// 声明的边数允许等于实际边数或其两倍（部分数据集按有向边计数）
+fn parse_dimacs(reader: impl BufRead, options: &DimacsOptions) -> Result<DimacsFile, GraphErr> {
+    let mut parser = DimacsParser::new(options);
+    for (line_no, line) in reader.lines().enumerate() {
+        parser.line(line_no + 1, &line?)?;
+    }
+    parser.finish()
+}
+
+// DIMACS 二进制格式（.clq.b）：
+// 第一行为十进制的头部字节数，随后是 ASCII 头部（注释与 p 行），
+// 最后是下三角邻接矩阵，第 i 行（从 0 开始）占 i / 8 + 1 字节，第 j 位（j <= i）的掩码为 0x80 >> (j % 8)
+fn parse_dimacs_binary(
+    mut reader: impl BufRead,
+    options: &DimacsOptions,
+) -> Result<DimacsFile, GraphErr> {
+    let mut first = String::new();
+    reader.read_line(&mut first)?;
+    let header_len: usize = first
+        .trim()
+        .parse()
+        .map_err(|_| error(1, 1, ParseErrorKind::InvalidNumber(first.trim().into())))?;
+
+    // 不按声明的长度预先分配，避免恶意输入耗尽内存
+    let mut header = Vec::new();
+    reader
+        .by_ref()
+        .take(header_len as u64)
+        .read_to_end(&mut header)?;
+    if header.len() < header_len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    let header = String::from_utf8_lossy(&header);
+    let mut parser = DimacsParser::new(options);
+    let mut lines = 1;
+    for line in header.lines() {
+        lines += 1;
+        parser.line(lines, line)?;
+    }
+    let Some(problem) = parser.problem else {
+        return Err(error(lines + 1, 1, ParseErrorKind::MissingProblemLine));
+    };
+
+    let mut row = Vec::new();
+    for i in 0..problem.node_count {
+        row.resize(i / 8 + 1, 0);
+        reader.read_exact(&mut row).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => error(lines + 1, 1, ParseErrorKind::TruncatedBitmap(i)),
+            _ => e.into(),
+        })?;
+        for (byte_idx, &byte) in row.iter().enumerate() {
+            for bit in 0..8 {
+                let j = byte_idx * 8 + bit;
+                if j <= i && byte & (0x80 >> bit) != 0 {
+                    parser.edge(lines + 1, 1, j + 1, i + 1, false)?;
+                }
+            }
+        }
+    }
+    parser.finish()
+}
+
+#[derive(Clone, Copy)]
+struct Problem {
+    line: usize,
+    node_count: usize,
+    edge_count: usize,
+}
+
+// 逐行解析 ASCII DIMACS，二进制格式的头部也由它处理
+struct DimacsParser<'o> {
+    options: &'o DimacsOptions,
+    graph: UnGraph<(), ()>,
+    problem: Option<Problem>,
+    seen: HashSet<(usize, usize)>,
+    edges: usize,
+    comments: Vec<String>,
+}
+
+impl<'o> DimacsParser<'o> {
+    fn new(options: &'o DimacsOptions) -> Self {
+        DimacsParser {
+            options,
+            graph: UnGraph::new_undirected(),
+            problem: None,
+            seen: HashSet::new(),
+            edges: 0,
+            comments: Vec::new(),
+        }
+    }
+
+    fn line(&mut self, line_no: usize, line: &str) -> Result<(), GraphErr> {
+        let mut tokens = Tokens::new(line, line_no);
+        let Some((column, kind)) = tokens.next() else {
+            return Ok(());
+        };
+
+        match kind {
+            "c" => {
+                let comment = &line[column..];
+                self.comments
+                    .push(comment.strip_prefix(' ').unwrap_or(comment).into());
+                Ok(())
+            }
+            // p edge 200 13089
+            "p" => {
+                if self.problem.is_some() {
+                    return Err(error(line_no, column, ParseErrorKind::DuplicateProblemLine));
+                }
+                let (column, format) = tokens.expect("problem format")?;
+                if format != "edge" && format != "col" {
+                    return Err(error(
+                        line_no,
+                        column,
+                        ParseErrorKind::UnsupportedFormat(format.into()),
+                    ));
+                }
+                let count_column = tokens.peek_column();
+                let node_count = tokens.number("vertex count")?;
+                let edge_count = tokens.number("edge count")?;
+                tokens.end()?;
+                // petgraph 的节点下标为 u32，最大值保留作无效下标
+                if node_count > self.options.max_vertices
+                    || node_count >= <u32 as IndexType>::max().index()
+                {
+                    return Err(error(
+                        line_no,
+                        count_column,
+                        ParseErrorKind::TooManyVertices(node_count),
+                    ));
+                }
+                self.graph = UnGraph::with_capacity(node_count, 0);
+                for _ in 0..node_count {
+                    self.graph.add_node(());
+                }
+                self.problem = Some(Problem {
+                    line: line_no,
+                    node_count,
+                    edge_count,
+                });
+                Ok(())
+            }
+            // e 10 2
+            "e" => {
+                let Some(Problem { node_count, .. }) = self.problem else {
+                    return Err(error(line_no, column, ParseErrorKind::MissingProblemLine));
+                };
+                let u = tokens.vertex(node_count)?;
+                let v = tokens.vertex(node_count)?;
+                tokens.end()?;
+                self.edge(line_no, column, u, v, true)
+            }
+            other => Err(error(
+                line_no,
+                column,
+                ParseErrorKind::UnexpectedToken(other.into()),
+            )),
+        }
+    }
+
+    // 顶点编号从 1 开始，二进制格式中不可能出现重复边，无需检查
+    fn edge(
+        &mut self,
+        line_no: usize,
+        column: usize,
+        u: usize,
+        v: usize,
+        check_duplicate: bool,
+    ) -> Result<(), GraphErr> {
+        self.edges += 1;
+        let duplicate = if u == v {
+            Some(ParseErrorKind::SelfLoop(u))
+        } else if check_duplicate && !self.seen.insert((u.min(v), u.max(v))) {
+            Some(ParseErrorKind::DuplicateEdge(u, v))
+        } else {
+            None
+        };
+        match duplicate {
+            Some(_) if self.options.dedup => {}
+            Some(kind) => return Err(error(line_no, column, kind)),
+            None => {
+                self.graph
+                    .add_edge(NodeIndex::new(u - 1), NodeIndex::new(v - 1), ());
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<DimacsFile, GraphErr> {
+        if let Some(Problem {
+            line, edge_count, ..
+        }) = self.problem
+            && !self.options.skip_edge_count_check
+            && edge_count != self.edges
+            && edge_count != 2 * self.edges
+        {
+            return Err(error(
+                line,
+                1,
+                ParseErrorKind::EdgeCountMismatch {
+                    declared: edge_count,
+                    actual: self.edges,
+                },
+            ));
+        }
+        Ok(DimacsFile {
+            graph: self.graph,
+            comments: self.comments,
+        })
+    }
+}
+
+// 支持的输入格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    // DIMACS ASCII 或二进制格式
+    Dimacs,
+    // SNAP 风格的边列表，`#` 或 `%` 开头为注释，节点 ID 可以是任意不含空白的字符串
+    Snap,
+    // METIS 邻接表（.graph）
+    Metis,
+    // Matrix Market 坐标格式（.mtx），行列下标即节点编号
+    MatrixMarket,
+    GraphMl,
+    // PACE 挑战赛的 .gr 格式
+    Pace,
+}
 
-    for line in reader.lines() {
+impl Format {
+    // 命令行与 api 中使用的格式名称
+    pub fn from_name(name: &str) -> Option<Format> {
+        Some(match name {
+            "dimacs" => Format::Dimacs,
+            "snap" | "edges" => Format::Snap,
+            "metis" => Format::Metis,
+            "mtx" => Format::MatrixMarket,
+            "graphml" => Format::GraphMl,
+            "pace" => Format::Pace,
+            _ => return None,
+        })
+    }
+
+    // 根据扩展名判断，会先去掉 .gz / .xz / .zst 后缀
+    pub fn from_path(path: &str) -> Option<Format> {
+        let path = path.to_ascii_lowercase();
+        let path = [".gz", ".xz", ".zst"]
+            .iter()
+            .find_map(|ext| path.strip_suffix(ext))
+            .unwrap_or(&path);
+        let ext = path.rsplit_once('.')?.1;
+        Some(match ext {
+            "clq" | "col" | "dimacs" | "b" => Format::Dimacs,
+            "txt" | "edges" | "el" | "tsv" | "snap" => Format::Snap,
+            "graph" | "metis" => Format::Metis,
+            "mtx" => Format::MatrixMarket,
+            "graphml" | "xml" => Format::GraphMl,
+            "gr" => Format::Pace,
+            _ => return None,
+        })
+    }
+
+    // 根据（解压后的）文件开头判断，无法判断时按 SNAP 边列表处理
+    // 没有 `%` 注释的 METIS 文件无法与边列表区分，需要扩展名或显式指定；
+    // KONECT 等边列表同样以 `%` 注释开头，见 looks_like_metis
+    pub fn detect(head: &[u8]) -> Format {
+        let head = String::from_utf8_lossy(head);
+        let head = head.trim_start_matches('\u{feff}').trim_start();
+        if head.starts_with("%%MatrixMarket") {
+            return Format::MatrixMarket;
+        }
+        if head.starts_with('<') {
+            return Format::GraphMl;
+        }
+        for line in head.lines() {
+            let mut tokens = line.split_whitespace();
+            match (tokens.next(), tokens.next()) {
+                (None, _) | (Some("c"), _) => continue,
+                (Some("p"), Some("edge" | "col")) => return Format::Dimacs,
+                (Some("p"), _) => return Format::Pace,
+                (Some("e"), _) => return Format::Dimacs,
+                // 二进制 DIMACS 的第一行只有头部长度
+                (Some(first), None) if first.bytes().all(|b| b.is_ascii_digit()) => {
+                    return Format::Dimacs;
+                }
+                (Some(first), _) if first.starts_with('%') && looks_like_metis(head) => {
+                    return Format::Metis;
+                }
+                _ => return Format::Snap,
+            }
+        }
+        Format::Snap
+    }
+}
+
+// 第一个非注释行是合法的 METIS 头部，并且之后的行数不超过声明的节点数
+// head 可能在任意位置截断，只能排除行数超出的情况
+fn looks_like_metis(head: &str) -> bool {
+    let mut lines = head
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('%'))
+        .skip_while(|line| line.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return false;
+    };
+    let tokens: Vec<&str> = header.split_whitespace().collect();
+    if !(2..=4).contains(&tokens.len()) || tokens.iter().any(|t| t.parse::<usize>().is_err()) {
+        return false;
+    }
+    let fmt = tokens.get(2).copied().unwrap_or("0");
+    if fmt.len() > 3 || !fmt.bytes().all(|b| b == b'0' || b == b'1') {
+        return false;
+    }
+    // 邻接表中的空行是孤立节点，只有末尾的空行不计
+    let rows: Vec<&str> = lines.collect();
+    let rows = rows
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |i| i + 1);
+    rows <= tokens[0].parse::<usize>().unwrap()
+}
+
+// 带有原始节点标签的图，labels[i] 是节点 i 在源文件中的 ID
+// 按编号存储的格式（DIMACS、METIS、Matrix Market、PACE）标签为从 1 开始的编号
+#[derive(Debug, Clone, Default)]
+pub struct LabeledGraph {
+    pub graph: UnGraph<(), ()>,
+    pub labels: Vec<String>,
+}
+
+impl LabeledGraph {
+    pub fn label(&self, node: NodeIndex) -> &str {
+        &self.labels[node.index()]
+    }
+
+    pub fn labels_of(&self, nodes: &[NodeIndex]) -> Vec<&str> {
+        nodes.iter().map(|&u| self.label(u)).collect()
+    }
+}
+
+// 按扩展名判断格式，判断不了时根据内容判断
+pub fn read_graph(path: &str) -> Result<LabeledGraph, GraphErr> {
+    read_graph_from(BufReader::new(File::open(path)?), Format::from_path(path))
+}
+
+pub fn read_graph_as(path: &str, format: Format) -> Result<LabeledGraph, GraphErr> {
+    read_graph_from(BufReader::new(File::open(path)?), Some(format))
+}
+
+// read_graph 系列的读取选项
+#[derive(Debug, Clone)]
+pub struct ReadOptions {
+    max_vertices: usize,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        ReadOptions {
+            max_vertices: DEFAULT_MAX_VERTICES,
+        }
+    }
+}
+
+impl ReadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 按编号存储的格式（DIMACS、METIS、Matrix Market、PACE）允许声明的最大节点数，
+    // 声明的节点会预先创建，处理不可信的输入时应设得更小
+    pub fn max_vertices(mut self, max_vertices: usize) -> Self {
+        self.max_vertices = max_vertices;
+        self
+    }
+}
+
+// format 为 None 时根据内容判断；除 DIMACS 外，自环和重复边（包括反向重复）会被直接忽略
+pub fn read_graph_from<R: BufRead>(
+    reader: R,
+    format: Option<Format>,
+) -> Result<LabeledGraph, GraphErr> {
+    read_graph_from_with(reader, format, &ReadOptions::default())
+}
+
+pub fn read_graph_from_with<R: BufRead>(
+    reader: R,
+    format: Option<Format>,
+    options: &ReadOptions,
+) -> Result<LabeledGraph, GraphErr> {
+    let max_vertices = options.max_vertices;
+    let mut reader = decompress(reader)?;
+    skip_bom(&mut reader)?;
+    let format = match format {
+        Some(format) => format,
+        None => Format::detect(reader.fill_buf()?),
+    };
+    match format {
+        Format::Dimacs => {
+            let options = DimacsOptions::new().max_vertices(max_vertices);
+            let graph = read_dimacs_from_with(reader, &options)?;
+            let labels = (1..=graph.node_count()).map(|i| i.to_string()).collect();
+            Ok(LabeledGraph { graph, labels })
+        }
+        Format::Snap => parse_snap(reader),
+        Format::Metis => parse_metis(reader, max_vertices),
+        Format::MatrixMarket => parse_matrix_market(reader, max_vertices),
+        Format::GraphMl => parse_graphml(reader),
+        Format::Pace => parse_pace(reader, max_vertices),
+    }
+}
+
+// 构建带标签的图，忽略自环与重复边
+#[derive(Default)]
+struct LabeledBuilder {
+    labeled: LabeledGraph,
+    index: HashMap<String, NodeIndex>,
+    seen: HashSet<(usize, usize)>,
+}
+
+impl LabeledBuilder {
+    // 节点按编号 1..=node_count 预先创建，超过上限时在分配之前报错
+    fn numbered(
+        line: usize,
+        column: usize,
+        node_count: usize,
+        max_vertices: usize,
+    ) -> Result<Self, GraphErr> {
+        if node_count > max_vertices || node_count >= <u32 as IndexType>::max().index() {
+            return Err(error(
+                line,
+                column,
+                ParseErrorKind::TooManyVertices(node_count),
+            ));
+        }
+        let mut builder = LabeledBuilder::default();
+        builder.labeled.graph = UnGraph::with_capacity(node_count, 0);
+        for i in 1..=node_count {
+            builder.labeled.graph.add_node(());
+            builder.labeled.labels.push(i.to_string());
+        }
+        Ok(builder)
+    }
+
+    fn node(&mut self, label: &str) -> Result<NodeIndex, ParseErrorKind> {
+        if let Some(&u) = self.index.get(label) {
+            return Ok(u);
+        }
+        if self.labeled.graph.node_count() + 1 >= <u32 as IndexType>::max().index() {
+            return Err(ParseErrorKind::TooManyVertices(
+                self.labeled.graph.node_count() + 1,
+            ));
+        }
+        let u = self.labeled.graph.add_node(());
+        self.labeled.labels.push(label.into());
+        self.index.insert(label.into(), u);
+        Ok(u)
+    }
+
+    // 下标从 0 开始
+    fn edge(&mut self, a: usize, b: usize) {
+        if a != b && self.seen.insert((a.min(b), a.max(b))) {
+            self.labeled
+                .graph
+                .add_edge(NodeIndex::new(a), NodeIndex::new(b), ());
+        }
+    }
+}
+
+// 跳过空行与注释行，返回 (行号, 内容)
+fn data_lines(
+    reader: impl BufRead,
+    is_comment: impl Fn(&str) -> bool,
+) -> impl Iterator<Item = Result<(usize, String), GraphErr>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(move |(i, line)| match line {
+            Ok(line) if line.trim().is_empty() || is_comment(line.trim_start()) => None,
+            Ok(line) => Some(Ok((i + 1, line))),
+            Err(e) => Some(Err(e.into())),
+        })
+}
+
+// 1 2
+// alice	bob
+fn parse_snap(reader: impl BufRead) -> Result<LabeledGraph, GraphErr> {
+    let mut builder = LabeledBuilder::default();
+    for line in data_lines(reader, |l| l.starts_with('#') || l.starts_with('%')) {
+        let (line_no, line) = line?;
+        let mut tokens = Tokens::new(&line, line_no);
+        let mut node = |tokens: &mut Tokens| {
+            let (column, label) = tokens.expect("node id")?;
+            builder
+                .node(label)
+                .map(NodeIndex::index)
+                .map_err(|kind| error(line_no, column, kind))
+        };
+        let a = node(&mut tokens)?;
+        let b = node(&mut tokens)?;
+        // 多出的列（权重、时间戳等）忽略
+        builder.edge(a, b);
+    }
+    Ok(builder.labeled)
+}
+
+// 首行 `n m [fmt [ncon]]`，随后 n 行邻接表（从 1 开始），`%` 开头为注释
+// fmt 的三位依次表示是否带有顶点大小、顶点权重、边权重，权重会被忽略
+fn parse_metis(reader: impl BufRead, max_vertices: usize) -> Result<LabeledGraph, GraphErr> {
+    let mut lines = reader.lines().enumerate();
+    // 邻接表中的空行表示孤立节点，只在找到首行之前跳过空行
+    let (line_no, header) = loop {
+        let Some((i, line)) = lines.next() else {
+            return Err(error(1, 1, ParseErrorKind::MissingToken("METIS header")));
+        };
         let line = line?;
-        // e 10 2
-        if line.starts_with('e') {
-            let stuff: Vec<&str> = line.split_ascii_whitespace().collect();
-            if stuff.len() != 3 {
-                return Err(GraphErr::ParseError("bad edge format!".into()));
-            }
-            let u: u32 = stuff[1].parse().unwrap();
-            let v: u32 = stuff[2].parse().unwrap();
-            let u_idx = *nodes.get(u as usize - 1).unwrap();
-            let v_ind = *nodes.get(v as usize - 1).unwrap();
-            graph.add_edge(u_idx, v_ind, ());
+        if !line.trim().is_empty() && !line.trim_start().starts_with('%') {
+            break (i + 1, line);
+        }
+    };
+
+    let mut tokens = Tokens::new(&header, line_no);
+    let count_column = tokens.peek_column();
+    let node_count = tokens.number("vertex count")?;
+    let edge_count = tokens.number("edge count")?;
+    let fmt_column = tokens.peek_column();
+    let fmt = tokens.next().map_or("0", |(_, fmt)| fmt);
+    let ncon = if tokens.at_end() {
+        1
+    } else {
+        tokens.number("ncon")?
+    };
+    tokens.end()?;
+    if fmt.len() > 3 || !fmt.bytes().all(|b| b == b'0' || b == b'1') {
+        return Err(error(
+            line_no,
+            fmt_column,
+            ParseErrorKind::InvalidHeader(format!("fmt `{fmt}`")),
+        ));
+    }
+    let flag = |i: usize| fmt.len() > i && fmt.as_bytes()[fmt.len() - 1 - i] == b'1';
+    let (edge_weights, vertex_weights, vertex_sizes) = (flag(0), flag(1), flag(2));
+
+    let mut builder = LabeledBuilder::numbered(line_no, count_column, node_count, max_vertices)?;
+    let mut u = 0;
+    for (i, line) in lines {
+        let line = line?;
+        let line_no = i + 1;
+        if line.trim_start().starts_with('%') {
+            continue;
+        }
+        let mut tokens = Tokens::new(&line, line_no);
+        if u == node_count {
+            if let Some((column, token)) = tokens.next() {
+                return Err(error(
+                    line_no,
+                    column,
+                    ParseErrorKind::UnexpectedToken(token.into()),
+                ));
+            }
             continue;
         }
-        // p edge 200 13089
-        if line.starts_with('p') {
-            let stuff: Vec<&str> = line.split_ascii_whitespace().collect();
-            let node_count: usize = stuff[2].parse().unwrap();
-            nodes = (0..node_count).map(|_| graph.add_node(())).collect();
+        if vertex_sizes {
+            tokens.number("vertex size")?;
+        }
+        if vertex_weights {
+            for _ in 0..ncon {
+                tokens.number("vertex weight")?;
+            }
+        }
+        while !tokens.at_end() {
+            let v = tokens.vertex(node_count)?;
+            if edge_weights {
+                tokens.number("edge weight")?;
+            }
+            builder.edge(u, v - 1);
+        }
+        u += 1;
+    }
+    if u < node_count {
+        return Err(error(
+            line_no,
+            count_column,
+            ParseErrorKind::NodeCountMismatch {
+                declared: node_count,
+                actual: u,
+            },
+        ));
+    }
+    if builder.seen.len() != edge_count {
+        return Err(error(
+            line_no,
+            count_column,
+            ParseErrorKind::EdgeCountMismatch {
+                declared: edge_count,
+                actual: builder.seen.len(),
+            },
+        ));
+    }
+    Ok(builder.labeled)
+}
+
+// %%MatrixMarket matrix coordinate pattern symmetric
+// 只支持 coordinate 存储，值类型为 pattern / real / integer，对称性为 symmetric / general，值本身忽略
+// 随后是 `行数 列数 非零元个数` 与 `i j [值]`，矩阵必须是方阵
+fn parse_matrix_market(
+    mut reader: impl BufRead,
+    max_vertices: usize,
+) -> Result<LabeledGraph, GraphErr> {
+    let mut banner = String::new();
+    reader.read_line(&mut banner)?;
+    let words: Vec<String> = banner
+        .split_whitespace()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    let supported = words.len() == 5
+        && words[0] == "%%matrixmarket"
+        && words[1] == "matrix"
+        && words[2] == "coordinate"
+        && ["pattern", "real", "integer"].contains(&words[3].as_str())
+        && ["symmetric", "general"].contains(&words[4].as_str());
+    if !supported {
+        return Err(error(
+            1,
+            1,
+            ParseErrorKind::InvalidHeader(banner.trim().into()),
+        ));
+    }
+
+    // 第一行已经读出，行号顺延
+    let mut data =
+        data_lines(reader, |l| l.starts_with('%')).map(|line| line.map(|(i, line)| (i + 1, line)));
+    let Some(size) = data.next() else {
+        return Err(error(2, 1, ParseErrorKind::MissingToken("matrix size")));
+    };
+    let (line_no, size) = size?;
+    let mut tokens = Tokens::new(&size, line_no);
+    let rows = tokens.number("row count")?;
+    let cols = tokens.number("column count")?;
+    let entries = tokens.number("entry count")?;
+    tokens.end()?;
+    if rows != cols {
+        return Err(error(
+            line_no,
+            1,
+            ParseErrorKind::InvalidHeader(format!("matrix is {rows}x{cols}, not square")),
+        ));
+    }
+
+    let mut builder = LabeledBuilder::numbered(line_no, 1, rows, max_vertices)?;
+    let mut count = 0;
+    for line in data {
+        let (line_no, line) = line?;
+        let mut tokens = Tokens::new(&line, line_no);
+        let i = tokens.vertex(rows)?;
+        let j = tokens.vertex(rows)?;
+        builder.edge(i - 1, j - 1);
+        count += 1;
+    }
+    if count != entries {
+        return Err(error(
+            line_no,
+            1,
+            ParseErrorKind::EdgeCountMismatch {
+                declared: entries,
+                actual: count,
+            },
+        ));
+    }
+    Ok(builder.labeled)
+}
+
+// c 注释
+// p tw 5 6
+// 1 2
+fn parse_pace(reader: impl BufRead, max_vertices: usize) -> Result<LabeledGraph, GraphErr> {
+    let mut builder: Option<(usize, usize, LabeledBuilder)> = None;
+    let mut count = 0;
+    for line in data_lines(reader, |l| l.starts_with('c')) {
+        let (line_no, line) = line?;
+        let mut tokens = Tokens::new(&line, line_no);
+        match &mut builder {
+            None => {
+                let (column, p) = tokens.expect("problem line")?;
+                if p != "p" {
+                    return Err(error(line_no, column, ParseErrorKind::MissingProblemLine));
+                }
+                tokens.expect("problem descriptor")?;
+                let count_column = tokens.peek_column();
+                let node_count = tokens.number("vertex count")?;
+                let edge_count = tokens.number("edge count")?;
+                tokens.end()?;
+                let numbered =
+                    LabeledBuilder::numbered(line_no, count_column, node_count, max_vertices)?;
+                builder = Some((line_no, edge_count, numbered));
+            }
+            Some((_, _, numbered)) => {
+                let node_count = numbered.labeled.graph.node_count();
+                let u = tokens.vertex(node_count)?;
+                let v = tokens.vertex(node_count)?;
+                tokens.end()?;
+                numbered.edge(u - 1, v - 1);
+                count += 1;
+            }
+        }
+    }
+    let Some((line_no, edge_count, builder)) = builder else {
+        return Ok(LabeledGraph::default());
+    };
+    if count != edge_count {
+        return Err(error(
+            line_no,
+            1,
+            ParseErrorKind::EdgeCountMismatch {
+                declared: edge_count,
+                actual: count,
+            },
+        ));
+    }
+    Ok(builder.labeled)
+}
+
+// 只读取 <node id> 与 <edge source target>，其它元素（属性、数据）忽略
+fn parse_graphml(mut reader: impl BufRead) -> Result<LabeledGraph, GraphErr> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut xml = quick_xml::Reader::from_str(&text);
+    let mut builder = LabeledBuilder::default();
+    // 字节偏移转换为行列号
+    let at = |offset: u64, kind: ParseErrorKind| {
+        let before = &text[..(offset as usize).min(text.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        error(line, column, kind)
+    };
+    // 边可以出现在节点声明之前，读完整个文件后再解析端点
+    let mut edges = Vec::new();
+
+    loop {
+        let position = xml.buffer_position();
+        let event = xml
+            .read_event()
+            .map_err(|e| at(xml.error_position(), ParseErrorKind::Xml(e.to_string())))?;
+        let element = match &event {
+            Event::Start(e) | Event::Empty(e) => e,
+            Event::Eof => break,
+            _ => continue,
+        };
+        let attribute = |name: &'static str| -> Result<Option<String>, GraphErr> {
+            match element.try_get_attribute(name) {
+                Ok(Some(attr)) => attr
+                    .unescape_value()
+                    .map(|v| Some(v.into_owned()))
+                    .map_err(|e| at(position, ParseErrorKind::Xml(e.to_string()))),
+                Ok(None) => Ok(None),
+                Err(e) => Err(at(position, ParseErrorKind::Xml(e.to_string()))),
+            }
+        };
+        match element.local_name().as_ref() {
+            b"node" => {
+                let Some(id) = attribute("id")? else {
+                    return Err(at(position, ParseErrorKind::MissingToken("node id")));
+                };
+                builder.node(&id).map_err(|kind| at(position, kind))?;
+            }
+            b"edge" => {
+                let source = attribute("source")?;
+                let target = attribute("target")?;
+                let (Some(source), Some(target)) = (source, target) else {
+                    return Err(at(position, ParseErrorKind::MissingToken("edge endpoints")));
+                };
+                edges.push((position, source, target));
+            }
+            _ => {}
+        }
+    }
+    for (position, source, target) in edges {
+        let lookup = |id: String| match builder.index.get(&id) {
+            Some(u) => Ok(u.index()),
+            None => Err(at(position, ParseErrorKind::UnknownNode(id))),
+        };
+        let (a, b) = (lookup(source)?, lookup(target)?);
+        builder.edge(a, b);
+    }
+    Ok(builder.labeled)
+}
+
+fn error(line: usize, column: usize, kind: ParseErrorKind) -> GraphErr {
+    GraphErr::ParseError { line, column, kind }
+}
+
+// 按空白切分一行，同时记录每个词的列号（从 1 开始）
+struct Tokens<'a> {
+    line: &'a str,
+    line_no: usize,
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(line: &'a str, line_no: usize) -> Self {
+        Tokens {
+            line,
+            line_no,
+            pos: 0,
+        }
+    }
+
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        let rest = &self.line[self.pos..];
+        let start = self.pos + rest.find(|c: char| !c.is_whitespace())?;
+        let len = self.line[start..]
+            .find(char::is_whitespace)
+            .unwrap_or(self.line.len() - start);
+        self.pos = start + len;
+        Some((start + 1, &self.line[start..self.pos]))
+    }
+
+    // 下一个词的列号，没有时为行尾
+    fn peek_column(&self) -> usize {
+        let rest = &self.line[self.pos..];
+        self.pos
+            + rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len())
+            + 1
+    }
+
+    fn expect(&mut self, what: &'static str) -> Result<(usize, &'a str), GraphErr> {
+        let column = self.peek_column();
+        self.next()
+            .ok_or_else(|| error(self.line_no, column, ParseErrorKind::MissingToken(what)))
+    }
+
+    fn number(&mut self, what: &'static str) -> Result<usize, GraphErr> {
+        let (column, token) = self.expect(what)?;
+        token.parse().map_err(|_| {
+            error(
+                self.line_no,
+                column,
+                ParseErrorKind::InvalidNumber(token.into()),
+            )
+        })
+    }
+
+    fn at_end(&self) -> bool {
+        self.line[self.pos..].trim_start().is_empty()
+    }
+
+    // 从 1 开始编号的顶点
+    fn vertex(&mut self, node_count: usize) -> Result<usize, GraphErr> {
+        let column = self.peek_column();
+        let vertex = self.number("vertex")?;
+        if vertex == 0 || vertex > node_count {
+            return Err(error(
+                self.line_no,
+                column,
+                ParseErrorKind::VertexOutOfRange { vertex, node_count },
+            ));
+        }
+        Ok(vertex)
+    }
+
+    fn end(&mut self) -> Result<(), GraphErr> {
+        match self.next() {
+            Some((column, token)) => Err(error(
+                self.line_no,
+                column,
+                ParseErrorKind::UnexpectedToken(token.into()),
+            )),
+            None => Ok(()),
         }
     }
-    Ok(graph)
 }
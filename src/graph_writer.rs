@@ -0,0 +1,132 @@
+use petgraph::graph::{NodeIndex, UnGraph};
+use petgraph::visit::EdgeRef;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+// 所有写出函数都会忽略自环与重复边，保证输出可以被 graph_reader 按默认选项读回
+
+// 去掉自环与重复边后的边（0 开始的下标，较小的在前），保持原有顺序
+fn unique_edges<N, E>(graph: &UnGraph<N, E>) -> Vec<(usize, usize)> {
+    let mut seen = HashSet::new();
+    graph
+        .edge_references()
+        .map(|edge| {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            (a.min(b), a.max(b))
+        })
+        .filter(|&(a, b)| a != b && seen.insert((a, b)))
+        .collect()
+}
+
+// DIMACS ASCII 格式，comments 逐行写成 `c ...`（可以来自 graph_reader::DimacsFile）
+pub fn write_dimacs<N, E, W: Write>(
+    graph: &UnGraph<N, E>,
+    comments: &[String],
+    mut writer: W,
+) -> io::Result<()> {
+    let edges = unique_edges(graph);
+    for comment in comments {
+        if comment.is_empty() {
+            writeln!(writer, "c")?;
+        } else {
+            writeln!(writer, "c {comment}")?;
+        }
+    }
+    writeln!(writer, "p edge {} {}", graph.node_count(), edges.len())?;
+    for (a, b) in edges {
+        writeln!(writer, "e {} {}", a + 1, b + 1)?;
+    }
+    writer.flush()
+}
+
+// 写出 DIMACS 二进制格式（.clq.b），格式说明见 graph_reader
+pub fn write_dimacs_binary<N, E, W: Write>(graph: &UnGraph<N, E>, mut writer: W) -> io::Result<()> {
+    let node_count = graph.node_count();
+    let edges = unique_edges(graph);
+    let mut rows: Vec<Vec<u8>> = (0..node_count).map(|i| vec![0; i / 8 + 1]).collect();
+    for &(j, i) in &edges {
+        rows[i][j / 8] |= 0x80 >> (j % 8);
+    }
+
+    let header = format!("p edge {node_count} {}\n", edges.len());
+    write!(writer, "{}\n{header}", header.len())?;
+    for row in &rows {
+        writer.write_all(row)?;
+    }
+    writer.flush()
+}
+
+// 每行一条边 `u v`，节点下标从 0 开始
+pub fn write_edge_list<N, E, W: Write>(graph: &UnGraph<N, E>, mut writer: W) -> io::Result<()> {
+    for (a, b) in unique_edges(graph) {
+        writeln!(writer, "{a} {b}")?;
+    }
+    writer.flush()
+}
+
+// METIS 邻接表格式：首行 `节点数 边数`，第 i 行为节点 i 的邻居（从 1 开始）
+pub fn write_metis<N, E, W: Write>(graph: &UnGraph<N, E>, mut writer: W) -> io::Result<()> {
+    let edges = unique_edges(graph);
+    let mut adjacency = vec![Vec::new(); graph.node_count()];
+    for &(a, b) in &edges {
+        adjacency[a].push(b + 1);
+        adjacency[b].push(a + 1);
+    }
+
+    writeln!(writer, "{} {}", graph.node_count(), edges.len())?;
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        let line: Vec<String> = neighbors.iter().map(usize::to_string).collect();
+        writeln!(writer, "{}", line.join(" "))?;
+    }
+    writer.flush()
+}
+
+// GraphML 无向图，节点 id 为 n0, n1, ...
+pub fn write_graphml<N, E, W: Write>(graph: &UnGraph<N, E>, mut writer: W) -> io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    writeln!(writer, r#"  <graph id="G" edgedefault="undirected">"#)?;
+    for u in 0..graph.node_count() {
+        writeln!(writer, r#"    <node id="n{u}"/>"#)?;
+    }
+    for (a, b) in unique_edges(graph) {
+        writeln!(writer, r#"    <edge source="n{a}" target="n{b}"/>"#)?;
+    }
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")?;
+    writer.flush()
+}
+
+// Graphviz DOT，团内的节点和边带有 clique=true 属性并标红
+pub fn write_dot<N, E, W: Write>(
+    graph: &UnGraph<N, E>,
+    clique: &[NodeIndex],
+    mut writer: W,
+) -> io::Result<()> {
+    let in_clique: HashSet<usize> = clique.iter().map(|u| u.index()).collect();
+
+    writeln!(writer, "graph G {{")?;
+    for u in 0..graph.node_count() {
+        if in_clique.contains(&u) {
+            writeln!(
+                writer,
+                "  {u} [clique=true, color=red, style=filled, fillcolor=\"#ffcccc\"];"
+            )?;
+        } else {
+            writeln!(writer, "  {u};")?;
+        }
+    }
+    for (a, b) in unique_edges(graph) {
+        if in_clique.contains(&a) && in_clique.contains(&b) {
+            writeln!(writer, "  {a} -- {b} [clique=true, color=red, penwidth=2];")?;
+        } else {
+            writeln!(writer, "  {a} -- {b};")?;
+        }
+    }
+    writeln!(writer, "}}")?;
+    writer.flush()
+}
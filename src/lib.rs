@@ -1,3 +1,14 @@
+pub mod benchmarks;
+pub mod constraints;
+pub mod graph;
 pub mod graph_reader;
+pub mod graph_writer;
 pub mod max_clique;
-pub mod ga;
\ No newline at end of file
+pub mod ga;
+pub mod mcs;
+pub mod portfolio;
+pub mod preprocess;
+pub mod solver;
+pub mod sparse;
+pub mod verify;
+pub mod weighted;
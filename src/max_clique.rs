@@ -1,63 +1,231 @@
 use fixedbitset::FixedBitSet;
-use petgraph::graph::{NodeIndex, UnGraph};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::{EdgeCount, NodeCount};
+use std::ops::ControlFlow;
 
-use crate::ga::find_max_cliques_with_ga;
+use crate::ga::{all_max_cliques_with_ga, top_k_cliques_with_ga};
+use crate::graph::{CliqueGraph, Compact, build_neighbors, edge_count};
+use crate::preprocess::Preprocess;
+use crate::solver::{SolverOptions, Stop, solve};
 
-pub fn find_max_cliques(graph: &UnGraph<(), ()>) -> Vec<NodeIndex> {
-    let (n, e) = (graph.node_count(), graph.edge_count());
-    let density = 2.0 * e as f64 / (n * (n - 1)) as f64;
+// 可选的最大团算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    // 根据图的规模和密度自动选择
+    #[default]
+    Auto,
+    // Bron-Kerbosch Pivot，精确
+    BronKerbosch,
+    // 基于着色上界的分支限界，精确
+    Mcs,
+    // 多线程的 Mcs，在 rayon 线程池中并行搜索
+    ParallelMcs,
+    // 核分解 + 邻域子问题，精确，不需要 n×n 的邻接矩阵，适合大规模稀疏图
+    Sparse,
+    // 遗传算法，近似
+    Genetic,
+    // 遗传算法与 Mcs 同时运行，遗传算法的解作为 Mcs 的下界，Mcs 跑完即得到证明
+    Portfolio,
+}
+
+impl Algorithm {
+    // 把 Auto 解析为实际运行的算法
+    pub fn resolve<G: NodeCount + EdgeCount>(self, graph: G) -> Algorithm {
+        self.resolve_for(graph.node_count(), graph.edge_count())
+    }
+
+    pub(crate) fn resolve_for(self, node_count: usize, edge_count: usize) -> Algorithm {
+        match self {
+            Algorithm::Auto if prefer_exact(node_count, edge_count) => {
+                if rayon::current_num_threads() > 1 {
+                    Algorithm::ParallelMcs
+                } else {
+                    Algorithm::Mcs
+                }
+            }
+            Algorithm::Auto if prefer_sparse(node_count, edge_count) => Algorithm::Sparse,
+            Algorithm::Auto => Algorithm::Genetic,
+            other => other,
+        }
+    }
+
+    // 按 name() 的名称解析，另外接受 bron-kerbosch 与 ga 两个别名
+    pub fn from_name(name: &str) -> Option<Algorithm> {
+        Some(match name {
+            "auto" => Algorithm::Auto,
+            "bk" | "bron-kerbosch" => Algorithm::BronKerbosch,
+            "mcs" => Algorithm::Mcs,
+            "parallel-mcs" => Algorithm::ParallelMcs,
+            "sparse" => Algorithm::Sparse,
+            "genetic" | "ga" => Algorithm::Genetic,
+            "portfolio" => Algorithm::Portfolio,
+            _ => return None,
+        })
+    }
+
+    // 命令行与 api 输出中使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Auto => "auto",
+            Algorithm::BronKerbosch => "bk",
+            Algorithm::Mcs => "mcs",
+            Algorithm::ParallelMcs => "parallel-mcs",
+            Algorithm::Sparse => "sparse",
+            Algorithm::Genetic => "genetic",
+            Algorithm::Portfolio => "portfolio",
+        }
+    }
+
+    // 是否能证明结果最优
+    pub fn is_exact(self) -> bool {
+        matches!(
+            self,
+            Algorithm::BronKerbosch
+                | Algorithm::Mcs
+                | Algorithm::ParallelMcs
+                | Algorithm::Sparse
+                | Algorithm::Portfolio
+        )
+    }
+}
+
+// 先用贪心下界约简图，再在约简后的图上自动选择算法
+pub fn find_max_cliques<G: CliqueGraph>(graph: G) -> Vec<G::NodeId> {
+    let options = SolverOptions::new().preprocess(Preprocess::Greedy);
+    solve(graph, &options).clique
+}
+
+pub fn find_max_cliques_by<G: CliqueGraph>(graph: G, algorithm: Algorithm) -> Vec<G::NodeId> {
+    solve(graph, &SolverOptions::new().algorithm(algorithm)).clique
+}
+
+// 找出所有规模最大的团（并列的最优解全部返回）
+pub fn find_all_max_cliques<G: CliqueGraph>(graph: G) -> Vec<Vec<G::NodeId>> {
+    let compact = Compact::new(graph);
+    let adjacency = &compact.adjacency;
+    let cliques = if prefer_bk(adjacency.len(), edge_count(adjacency)) {
+        let mut collector = AllMaximum::default();
+        run_bk(adjacency, &mut collector);
+        collector.cliques
+    } else {
+        all_max_cliques_with_ga(adjacency)
+    };
+    cliques
+        .into_iter()
+        .map(|clique| compact.ids(clique))
+        .collect()
+}
+
+// 按规模降序返回前 k 个互不相同的极大团
+pub fn find_top_k_cliques<G: CliqueGraph>(graph: G, k: usize) -> Vec<Vec<G::NodeId>> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let compact = Compact::new(graph);
+    let adjacency = &compact.adjacency;
+    let cliques = if prefer_bk(adjacency.len(), edge_count(adjacency)) {
+        let mut collector = TopK::new(k);
+        run_bk(adjacency, &mut collector);
+        collector.cliques
+    } else {
+        top_k_cliques_with_ga(adjacency, k)
+    };
+    cliques
+        .into_iter()
+        .map(|clique| compact.ids(clique))
+        .collect()
+}
 
-    // 对于小图，直接使用 bk
-    if n <= 50
+// 根据图的规模和密度判断 bk 能否在可接受时间内枚举
+pub(crate) fn prefer_bk(n: usize, e: usize) -> bool {
+    let density = density(n, e);
+
+    n <= 50
         || (n <= 100 && density <= 0.9)
         || (n <= 200 && density <= 0.7)
         || (n <= 500 && density <= 0.1)
-    {
-        return find_max_cliques_with_bk(graph);
-    }
+}
+
+// 着色上界剪枝强得多，能精确求解的范围比 bk 大
+// 阈值来自 data/ 中实例的实测：以下范围内均能在几分钟内得到最优解
+pub(crate) fn prefer_exact(n: usize, e: usize) -> bool {
+    let density = density(n, e);
 
-    // 大图使用遗传算法
-    return find_max_cliques_with_ga(graph);
+    n <= 200
+        // brock400_* 约一到两分钟；p_hat700-3 数分钟内仍证明不了，留给遗传算法
+        || (n <= 400 && density <= 0.8)
+        || (n <= 800 && density <= 0.55)
+        || (n <= 2000 && density <= 0.3)
+        || (n <= 5000 && density <= 0.1)
+        // 补图极稀疏时着色上界非常紧（如 MANN_a27）
+        || (n <= 400 && density >= 0.98)
 }
 
-fn find_max_cliques_with_bk(graph: &UnGraph<(), ()>) -> Vec<NodeIndex> {
-    let node_count = graph.node_count();
+// 规模超出 prefer_exact 但足够稀疏时，邻域子问题很小，稀疏算法可以精确求解
+// 稠密的大图上遗传算法的邻接矩阵本来也放得下
+pub(crate) fn prefer_sparse(n: usize, e: usize) -> bool {
+    density(n, e) <= 0.05
+}
 
-    // 1. 构建原始邻接表
-    let mut neighbors = vec![FixedBitSet::with_capacity(node_count); node_count];
-    for u in graph.node_indices() {
-        let idx = u.index();
-        for v in graph.neighbors(u) {
-            neighbors[idx].insert(v.index());
-        }
+fn density(n: usize, e: usize) -> f64 {
+    if n <= 1 {
+        return 0.0;
     }
+    2.0 * e as f64 / (n * (n - 1)) as f64
+}
 
-    // 2. 创建排序映射
-    let (sorted_nodes, old_to_new) = {
-        let mut nodes: Vec<usize> = (0..node_count).collect();
-        nodes.sort_unstable_by_key(|&u| -(neighbors[u].count_ones(..) as i32));
+pub(crate) fn find_max_cliques_with_bk_until(adjacency: &[Vec<usize>], stop: &Stop) -> Vec<usize> {
+    let mut collector = Stoppable {
+        inner: FixedBitSet::with_capacity(adjacency.len()),
+        stop,
+        sorted_nodes: Vec::new(),
+    };
+    let sorted_nodes = run_bk(adjacency, &mut collector);
 
-        // 创建旧索引到新索引的映射
-        let mut mapping = vec![0; node_count];
-        for (new_idx, &old_idx) in nodes.iter().enumerate() {
-            mapping[old_idx] = new_idx;
-        }
-        (nodes, mapping)
+    // 转换结果
+    map_back(&collector.inner, &sorted_nodes)
+}
+
+// 带约束的 bk：当前团从 required 出发，候选集限制在 allowed 中
+// initial 为已知满足约束的团，作为初始的最优解参与剪枝（均为原图编号）
+pub(crate) fn find_max_clique_with_bk_restricted(
+    adjacency: &[Vec<usize>],
+    required: &[usize],
+    allowed: &FixedBitSet,
+    initial: &[usize],
+) -> Vec<usize> {
+    let node_count = adjacency.len();
+    let (sorted_neighbors, sorted_nodes) = build_sorted_neighbors(adjacency);
+    let mut old_to_new = vec![0; node_count];
+    for (new_idx, &old_idx) in sorted_nodes.iter().enumerate() {
+        old_to_new[old_idx] = new_idx;
+    }
+    let remap = |nodes: &[usize]| {
+        let mut set = FixedBitSet::with_capacity(node_count);
+        set.extend(nodes.iter().map(|&u| old_to_new[u]));
+        set
     };
+    let allowed: Vec<usize> = allowed.ones().collect();
 
-    // 3. 构建排序后的邻接表
-    let sorted_neighbors: Vec<FixedBitSet> = sorted_nodes
-        .iter()
-        .map(|&old_idx| {
-            neighbors[old_idx]
-                .ones()
-                .map(|old_nb| old_to_new[old_nb])
-                .collect()
-        })
-        .collect();
+    let mut collector = remap(initial);
+    bron_kerbosch_pivot(
+        &sorted_neighbors,
+        &mut remap(required),
+        &mut remap(&allowed),
+        &mut FixedBitSet::with_capacity(node_count),
+        &mut collector,
+    );
 
-    // 4. 初始化集合
-    let mut max_clique = FixedBitSet::with_capacity(node_count);
+    map_back(&collector, &sorted_nodes)
+}
+
+// 在排序后的邻接表上运行 bk，返回 新索引 -> 原索引 的映射
+fn run_bk(adjacency: &[Vec<usize>], collector: &mut impl CliqueCollector) -> Vec<usize> {
+    let node_count = adjacency.len();
+    let (sorted_neighbors, sorted_nodes) = build_sorted_neighbors(adjacency);
+    collector.set_mapping(&sorted_nodes);
+
+    // 初始化集合
     let mut candidates = FixedBitSet::from_iter(0..node_count);
     let mut excluded = FixedBitSet::with_capacity(node_count);
 
@@ -66,13 +234,165 @@ fn find_max_cliques_with_bk(graph: &UnGraph<(), ()>) -> Vec<NodeIndex> {
         &mut FixedBitSet::with_capacity(node_count),
         &mut candidates,
         &mut excluded,
-        &mut max_clique,
+        collector,
     );
 
-    // 5. 转换结果
-    max_clique
+    sorted_nodes
+}
+
+// bk 搜索过程中接收极大团的容器，同时决定剪枝的界
+trait CliqueCollector {
+    // 一个至多能扩展到 reachable 个节点的分支是否还值得搜索
+    fn worth(&self, reachable: usize) -> bool;
+    // 接收一个极大团（排序后的索引）
+    fn collect(&mut self, clique: &FixedBitSet);
+    // 需要原始索引的容器在这里保存映射
+    fn set_mapping(&mut self, _sorted_nodes: &[usize]) {}
+}
+
+// 只保留一个最大团
+impl CliqueCollector for FixedBitSet {
+    fn worth(&self, reachable: usize) -> bool {
+        reachable > self.count_ones(..)
+    }
+
+    fn collect(&mut self, clique: &FixedBitSet) {
+        if clique.count_ones(..) > self.count_ones(..) {
+            self.clone_from(clique);
+        }
+    }
+}
+
+// 停止后不再认为任何分支值得搜索，递归会迅速退出
+// 收到的团同时交给 stop 报告进度
+struct Stoppable<'s, C> {
+    inner: C,
+    stop: &'s Stop,
+    sorted_nodes: Vec<usize>,
+}
+
+impl<C: CliqueCollector> CliqueCollector for Stoppable<'_, C> {
+    fn worth(&self, reachable: usize) -> bool {
+        !self.stop.should_stop() && self.inner.worth(reachable)
+    }
+
+    fn collect(&mut self, clique: &FixedBitSet) {
+        self.inner.collect(clique);
+        self.stop.improved(
+            clique.count_ones(..),
+            clique.ones().map(|u| self.sorted_nodes[u]),
+        );
+    }
+
+    fn set_mapping(&mut self, sorted_nodes: &[usize]) {
+        self.sorted_nodes = sorted_nodes.to_vec();
+        self.inner.set_mapping(sorted_nodes);
+    }
+}
+
+// 保留所有规模并列最大的团，剪枝界从 <= 放宽为 <
+#[derive(Default)]
+struct AllMaximum {
+    size: usize,
+    cliques: Vec<Vec<usize>>,
+    sorted_nodes: Vec<usize>,
+}
+
+impl CliqueCollector for AllMaximum {
+    fn worth(&self, reachable: usize) -> bool {
+        reachable >= self.size
+    }
+
+    fn collect(&mut self, clique: &FixedBitSet) {
+        let size = clique.count_ones(..);
+        if size > self.size {
+            self.size = size;
+            self.cliques.clear();
+        }
+        if size == self.size {
+            self.cliques.push(map_back(clique, &self.sorted_nodes));
+        }
+    }
+
+    fn set_mapping(&mut self, sorted_nodes: &[usize]) {
+        self.sorted_nodes = sorted_nodes.to_vec();
+    }
+}
+
+// 保留规模最大的 k 个极大团，按规模降序
+struct TopK {
+    k: usize,
+    cliques: Vec<Vec<usize>>,
+    sorted_nodes: Vec<usize>,
+}
+
+impl TopK {
+    fn new(k: usize) -> Self {
+        TopK {
+            k,
+            cliques: Vec::with_capacity(k),
+            sorted_nodes: Vec::new(),
+        }
+    }
+}
+
+impl CliqueCollector for TopK {
+    fn worth(&self, reachable: usize) -> bool {
+        self.cliques.len() < self.k || reachable > self.cliques[self.k - 1].len()
+    }
+
+    fn collect(&mut self, clique: &FixedBitSet) {
+        let size = clique.count_ones(..);
+        if !self.worth(size) {
+            return;
+        }
+        // 同规模的团按发现顺序排列
+        let pos = self.cliques.partition_point(|c| c.len() >= size);
+        self.cliques
+            .insert(pos, map_back(clique, &self.sorted_nodes));
+        self.cliques.truncate(self.k);
+    }
+
+    fn set_mapping(&mut self, sorted_nodes: &[usize]) {
+        self.sorted_nodes = sorted_nodes.to_vec();
+    }
+}
+
+fn map_back(clique: &FixedBitSet, sorted_nodes: &[usize]) -> Vec<usize> {
+    clique
         .ones()
-        .map(|sorted_idx| NodeIndex::new(sorted_nodes[sorted_idx]))
+        .map(|sorted_idx| sorted_nodes[sorted_idx])
+        .collect()
+}
+
+// 构建按度数降序重新编号的邻接位集
+// 返回 (排序后的邻接表, 新索引 -> 原索引)
+pub(crate) fn build_sorted_neighbors(adjacency: &[Vec<usize>]) -> (Vec<FixedBitSet>, Vec<usize>) {
+    let neighbors = build_neighbors(adjacency);
+
+    let mut sorted_nodes: Vec<usize> = (0..neighbors.len()).collect();
+    sorted_nodes.sort_unstable_by_key(|&u| std::cmp::Reverse(adjacency[u].len()));
+
+    (reorder_neighbors(&neighbors, &sorted_nodes), sorted_nodes)
+}
+
+// 按 order（新索引 -> 原索引）重新编号邻接表
+pub(crate) fn reorder_neighbors(neighbors: &[FixedBitSet], order: &[usize]) -> Vec<FixedBitSet> {
+    let node_count = neighbors.len();
+
+    // 创建旧索引到新索引的映射
+    let mut old_to_new = vec![0; node_count];
+    for (new_idx, &old_idx) in order.iter().enumerate() {
+        old_to_new[old_idx] = new_idx;
+    }
+
+    order
+        .iter()
+        .map(|&old_idx| {
+            let mut set = FixedBitSet::with_capacity(node_count);
+            set.extend(neighbors[old_idx].ones().map(|old_nb| old_to_new[old_nb]));
+            set
+        })
         .collect()
 }
 
@@ -81,36 +401,27 @@ fn bron_kerbosch_pivot(
     current_clique: &mut FixedBitSet,
     candidates: &mut FixedBitSet,
     excluded: &mut FixedBitSet,
-    max_clique: &mut FixedBitSet,
+    collector: &mut impl CliqueCollector,
 ) {
     // 预计算大小
     let current_size = current_clique.count_ones(..);
     let candidates_size = candidates.count_ones(..);
 
     // 剪枝条件
-    if current_size + candidates_size <= max_clique.count_ones(..) {
+    if !collector.worth(current_size + candidates_size) {
         return;
     }
 
     // 终止条件
     if candidates.is_clear() {
-        if excluded.is_clear() && current_size > max_clique.count_ones(..) {
-            max_clique.clone_from(current_clique);
+        if excluded.is_clear() {
+            collector.collect(current_clique);
         }
         return;
     }
 
-    // 选择枢轴
-    let pivot = select_pivot(candidates, excluded, neighbors);
-
-    // 生成remaining集合
-    let mut remaining = if let Some(p) = pivot {
-        let mut diff = candidates.clone();
-        diff.difference_with(&neighbors[p]);
-        diff
-    } else {
-        candidates.clone()
-    };
+    // 选择枢轴，生成remaining集合
+    let mut remaining = pivot_remaining(candidates, excluded, neighbors);
 
     // 遍历所有候选节点
     while let Some(u) = remaining.ones().next() {
@@ -119,7 +430,7 @@ fn bron_kerbosch_pivot(
         new_candidates.intersect_with(&neighbors[u]);
 
         // 提前剪枝
-        if current_size + 1 + new_candidates.count_ones(..) <= max_clique.count_ones(..) {
+        if !collector.worth(current_size + 1 + new_candidates.count_ones(..)) {
             candidates.remove(u);
             excluded.insert(u);
             remaining.remove(u);
@@ -137,7 +448,7 @@ fn bron_kerbosch_pivot(
             current_clique,
             &mut new_candidates,
             &mut new_excluded,
-            max_clique,
+            collector,
         );
 
         // 回溯
@@ -159,3 +470,122 @@ fn select_pivot(
         tmp.count_ones(..)
     })
 }
+
+// 惰性枚举图中所有极大团
+// 复用 bk 的度数排序位集邻接表，结果映射回调用方的节点 ID
+pub fn maximal_cliques<G: CliqueGraph>(graph: G) -> MaximalCliques<G::NodeId> {
+    let compact = Compact::new(graph);
+    let node_count = compact.adjacency.len();
+    let (neighbors, sorted_nodes) = build_sorted_neighbors(&compact.adjacency);
+
+    let mut stack = Vec::new();
+    if node_count > 0 {
+        let candidates = FixedBitSet::from_iter(0..node_count);
+        let excluded = FixedBitSet::with_capacity(node_count);
+        let remaining = pivot_remaining(&candidates, &excluded, &neighbors);
+        stack.push(Frame {
+            candidates,
+            excluded,
+            remaining,
+        });
+    }
+
+    MaximalCliques {
+        neighbors,
+        sorted_ids: compact.ids(sorted_nodes),
+        current_clique: Vec::new(),
+        stack,
+    }
+}
+
+// 以回调方式枚举极大团，回调返回 ControlFlow::Break 时提前停止
+// 返回是否被提前停止
+pub fn for_each_maximal_clique<G, F>(graph: G, mut f: F) -> bool
+where
+    G: CliqueGraph,
+    F: FnMut(&[G::NodeId]) -> ControlFlow<()>,
+{
+    for clique in maximal_cliques(graph) {
+        if f(&clique).is_break() {
+            return true;
+        }
+    }
+    false
+}
+
+// bk 递归的一层，显式保存在栈上以便逐个产出结果
+struct Frame {
+    candidates: FixedBitSet,
+    excluded: FixedBitSet,
+    remaining: FixedBitSet, // 本层还需要分支的节点（候选集去掉枢轴邻居）
+}
+
+pub struct MaximalCliques<Id = NodeIndex> {
+    neighbors: Vec<FixedBitSet>,
+    // 排序后的索引 -> 调用方的节点 ID
+    sorted_ids: Vec<Id>,
+    current_clique: Vec<usize>,
+    stack: Vec<Frame>,
+}
+
+impl<Id: Copy> Iterator for MaximalCliques<Id> {
+    type Item = Vec<Id>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let frame = self.stack.last_mut()?;
+
+            // 本层分支处理完毕，回溯
+            let Some(u) = frame.remaining.ones().next() else {
+                self.stack.pop();
+                self.current_clique.pop();
+                continue;
+            };
+            frame.remaining.remove(u);
+
+            let mut new_candidates = frame.candidates.clone();
+            new_candidates.intersect_with(&self.neighbors[u]);
+            let mut new_excluded = frame.excluded.clone();
+            new_excluded.intersect_with(&self.neighbors[u]);
+
+            frame.candidates.remove(u);
+            frame.excluded.insert(u);
+            self.current_clique.push(u);
+
+            // 终止条件
+            if new_candidates.is_clear() {
+                let clique = new_excluded.is_clear().then(|| {
+                    self.current_clique
+                        .iter()
+                        .map(|&sorted_idx| self.sorted_ids[sorted_idx])
+                        .collect()
+                });
+                self.current_clique.pop();
+                if clique.is_some() {
+                    return clique;
+                }
+                continue;
+            }
+
+            let remaining = pivot_remaining(&new_candidates, &new_excluded, &self.neighbors);
+            self.stack.push(Frame {
+                candidates: new_candidates,
+                excluded: new_excluded,
+                remaining,
+            });
+        }
+    }
+}
+
+// 候选集中与枢轴不相邻的节点
+fn pivot_remaining(
+    candidates: &FixedBitSet,
+    excluded: &FixedBitSet,
+    neighbors: &[FixedBitSet],
+) -> FixedBitSet {
+    let mut remaining = candidates.clone();
+    if let Some(p) = select_pivot(candidates, excluded, neighbors) {
+        remaining.difference_with(&neighbors[p]);
+    }
+    remaining
+}
@@ -0,0 +1,498 @@
+use fixedbitset::FixedBitSet;
+use petgraph::graph::{NodeIndex, UnGraph};
+use rayon::prelude::*;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::graph::{CliqueGraph, Compact, build_neighbors};
+use crate::max_clique::reorder_neighbors;
+use crate::solver::Stop;
+use crate::verify::{Proof, ProofStep};
+
+// 基于贪心着色上界的分支限界精确算法（MCS 风格）
+// 1. 按退化序（反复删除最小度节点）重新编号
+// 2. 每层对候选集贪心着色，颜色数即为团规模的上界
+// 3. 着色时尝试重新着色（Re-NUMBER），把高颜色节点挤进低颜色类以收紧上界
+pub fn find_max_cliques_with_mcs<G: CliqueGraph>(graph: G) -> Vec<G::NodeId> {
+    let compact = Compact::new(graph);
+    compact.ids(find_max_cliques_with_mcs_until(
+        &compact.adjacency,
+        0,
+        &Stop::never(),
+    ))
+}
+
+// lower_bound 为已知团的规模，只记录严格更大的团，找不到时返回空
+pub(crate) fn find_max_cliques_with_mcs_until(
+    adjacency: &[Vec<usize>],
+    lower_bound: usize,
+    stop: &Stop,
+) -> Vec<usize> {
+    let neighbors = build_neighbors(adjacency);
+    let order = degeneracy_order(&neighbors);
+    let sorted_neighbors = reorder_neighbors(&neighbors, &order);
+
+    let report = |clique: &[usize]| stop.improved(clique.len(), clique.iter().map(|&v| order[v]));
+    let bound = SharedBest::default();
+    bound.raise(lower_bound);
+    let mut mcs = Mcs::with_bound(&sorted_neighbors, &bound, stop, Vec::new());
+    mcs.report = Some(&report);
+    mcs.expand(FixedBitSet::from_iter(0..order.len()));
+
+    mcs.best
+        .iter()
+        .map(|&sorted_idx| order[sorted_idx])
+        .collect()
+}
+
+// 在求解的同时记录搜索树，可交给 verify::check_maximum 独立验证结果是最大团
+// 证明中的节点是 NodeIndex 的下标，所以只接受 UnGraph
+pub fn find_max_cliques_with_mcs_proof<N, E>(graph: &UnGraph<N, E>) -> (Vec<NodeIndex>, Proof) {
+    let neighbors = build_neighbors(&Compact::new(graph).adjacency);
+    let order = degeneracy_order(&neighbors);
+    let sorted_neighbors = reorder_neighbors(&neighbors, &order);
+
+    let mut mcs = Mcs::new(&sorted_neighbors);
+    mcs.proof = Some(Vec::new());
+    if !order.is_empty() {
+        mcs.expand(FixedBitSet::from_iter(0..order.len()));
+    }
+
+    // 映射回原索引
+    let map = |nodes: &[usize]| nodes.iter().map(|&v| order[v]).collect();
+    let steps = mcs
+        .proof
+        .unwrap()
+        .iter()
+        .map(|step| ProofStep {
+            classes: step.classes.iter().map(|class| map(class)).collect(),
+            branch: map(&step.branch),
+        })
+        .collect();
+    let clique = mcs.best.iter().map(|&v| NodeIndex::new(order[v])).collect();
+    (clique, Proof { steps })
+}
+
+// 退化序：反复删除度数最小的节点，最后删除的排在最前
+pub(crate) fn degeneracy_order(neighbors: &[FixedBitSet]) -> Vec<usize> {
+    let node_count = neighbors.len();
+    let mut degree: Vec<usize> = neighbors.iter().map(|nb| nb.count_ones(..)).collect();
+    let mut removed = vec![false; node_count];
+    let mut order = Vec::with_capacity(node_count);
+
+    // 桶排序维护当前度数
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+    let mut buckets = vec![Vec::new(); max_degree + 1];
+    for (u, &d) in degree.iter().enumerate() {
+        buckets[d].push(u);
+    }
+
+    let mut d = 0;
+    while order.len() < node_count {
+        d = d.min(max_degree);
+        let Some(u) = buckets[d].pop() else {
+            d += 1;
+            continue;
+        };
+        // 桶里可能有过期的记录
+        if removed[u] || degree[u] != d {
+            continue;
+        }
+        removed[u] = true;
+        order.push(u);
+        for v in neighbors[u].ones() {
+            if !removed[v] {
+                degree[v] -= 1;
+                buckets[degree[v]].push(v);
+            }
+        }
+        d = d.saturating_sub(1);
+    }
+
+    order.reverse();
+    order
+}
+
+// 多个线程共享的当前最优解，规模放在原子变量中供剪枝时无锁读取
+#[derive(Default)]
+pub(crate) struct SharedBest {
+    size: AtomicUsize,
+    clique: Mutex<Vec<usize>>,
+}
+
+impl SharedBest {
+    pub(crate) fn size(&self) -> usize {
+        self.size.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn offer(&self, clique: &[usize]) {
+        if clique.len() <= self.size() {
+            return;
+        }
+        let mut best = self.clique.lock().unwrap();
+        if clique.len() > best.len() {
+            best.clear();
+            best.extend_from_slice(clique);
+            self.size.store(clique.len(), Ordering::Relaxed);
+        }
+    }
+
+    // 只提高规模不记录团，用于仅作剪枝界的 SharedBest（例如来自遗传算法的下界）
+    pub(crate) fn raise(&self, size: usize) {
+        self.size.fetch_max(size, Ordering::Relaxed);
+    }
+
+    pub(crate) fn into_clique(self) -> Vec<usize> {
+        self.clique.into_inner().unwrap()
+    }
+}
+
+pub(crate) type Report<'a> = dyn Fn(&[usize]) + Sync + 'a;
+
+pub(crate) struct Mcs<'a> {
+    neighbors: &'a [FixedBitSet],
+    current: Vec<usize>,
+    pub(crate) best: Vec<usize>,
+    shared: Option<&'a SharedBest>,
+    // 只用于剪枝的外部下界，找到的解不会写入
+    bound: Option<&'a SharedBest>,
+    pub(crate) stop: Option<&'a Stop>,
+    // 找到更大的团时调用，用于报告进度
+    pub(crate) report: Option<&'a Report<'a>>,
+    // 需要证明时按先序记录每个搜索节点
+    pub(crate) proof: Option<Vec<ProofStep>>,
+    scratch: ColorScratch,
+}
+
+impl<'a> Mcs<'a> {
+    pub(crate) fn new(neighbors: &'a [FixedBitSet]) -> Self {
+        Mcs {
+            neighbors,
+            current: Vec::new(),
+            best: Vec::new(),
+            shared: None,
+            bound: None,
+            stop: None,
+            report: None,
+            proof: None,
+            scratch: ColorScratch::default(),
+        }
+    }
+
+    // 从给定的部分团出发搜索，最优解与其它线程共享
+    pub(crate) fn with_shared(
+        neighbors: &'a [FixedBitSet],
+        shared: &'a SharedBest,
+        stop: &'a Stop,
+        current: Vec<usize>,
+    ) -> Self {
+        Mcs {
+            current,
+            shared: Some(shared),
+            stop: Some(stop),
+            ..Mcs::new(neighbors)
+        }
+    }
+
+    // 用外部的最优解剪枝，但解由调用方映射后再提交
+    pub(crate) fn with_bound(
+        neighbors: &'a [FixedBitSet],
+        bound: &'a SharedBest,
+        stop: &'a Stop,
+        current: Vec<usize>,
+    ) -> Self {
+        Mcs {
+            current,
+            bound: Some(bound),
+            stop: Some(stop),
+            ..Mcs::new(neighbors)
+        }
+    }
+
+    fn best_len(&self) -> usize {
+        let shared = self.shared.or(self.bound).map_or(0, SharedBest::size);
+        self.best.len().max(shared)
+    }
+
+    pub(crate) fn expand(&mut self, mut candidates: FixedBitSet) {
+        if self.stop.is_some_and(Stop::should_stop) {
+            return;
+        }
+
+        // 颜色不小于 k_min 的节点才可能让当前团超过最优解
+        let k_min = (self.best_len() + 1).saturating_sub(self.current.len());
+        let (order, colors) = color_sort(self.neighbors, &candidates, k_min, &mut self.scratch);
+
+        // 先占位，子节点的记录排在后面，结束时再填入
+        let slot = self.proof.as_mut().map(|proof| {
+            proof.push(ProofStep::default());
+            (proof.len() - 1, self.scratch.low_classes())
+        });
+
+        // 从颜色最大的节点开始分支，order[..cut] 是被剪掉的节点
+        let mut cut = 0;
+        for (i, (&u, &color)) in order.iter().zip(&colors).enumerate().rev() {
+            // 剪枝条件
+            if self.current.len() + color <= self.best_len() {
+                cut = i + 1;
+                break;
+            }
+
+            let mut new_candidates = candidates.clone();
+            new_candidates.intersect_with(&self.neighbors[u]);
+
+            self.current.push(u);
+            if new_candidates.is_clear() {
+                if self.current.len() > self.best_len() {
+                    self.best.clone_from(&self.current);
+                    if let Some(shared) = self.shared {
+                        shared.offer(&self.current);
+                    }
+                    if let Some(report) = self.report {
+                        report(&self.current);
+                    }
+                }
+            } else {
+                self.expand(new_candidates);
+            }
+
+            // 回溯
+            self.current.pop();
+            candidates.remove(u);
+        }
+
+        if let (Some(proof), Some((slot, mut classes))) = (self.proof.as_mut(), slot) {
+            // 被剪掉的高颜色节点按颜色分组，与低颜色类一起构成着色
+            for (&u, &color) in order[..cut].iter().zip(&colors) {
+                if classes.len() < color {
+                    classes.push(Vec::new());
+                }
+                classes.last_mut().unwrap().push(u);
+            }
+            proof[slot] = ProofStep {
+                classes,
+                branch: order[cut..].to_vec(),
+            };
+        }
+    }
+}
+
+// 并行版本：前 PARALLEL_DEPTH 层的分支交给 rayon 线程池，由工作窃取平衡负载
+// 更深的子树在各线程内按串行 MCS 搜索，所有线程通过 SharedBest 共享剪枝界
+const PARALLEL_DEPTH: usize = 2;
+
+pub fn find_max_cliques_with_parallel_mcs<G: CliqueGraph>(graph: G) -> Vec<G::NodeId> {
+    let compact = Compact::new(graph);
+    compact.ids(find_max_cliques_with_parallel_mcs_until(
+        &compact.adjacency,
+        0,
+        &Stop::never(),
+    ))
+}
+
+pub(crate) fn find_max_cliques_with_parallel_mcs_until(
+    adjacency: &[Vec<usize>],
+    lower_bound: usize,
+    stop: &Stop,
+) -> Vec<usize> {
+    let neighbors = build_neighbors(adjacency);
+    let order = degeneracy_order(&neighbors);
+    let sorted_neighbors = reorder_neighbors(&neighbors, &order);
+
+    let report = |clique: &[usize]| stop.improved(clique.len(), clique.iter().map(|&v| order[v]));
+    let shared = SharedBest::default();
+    shared.raise(lower_bound);
+    par_expand(
+        &sorted_neighbors,
+        &shared,
+        stop,
+        &report,
+        Vec::new(),
+        FixedBitSet::from_iter(0..order.len()),
+        0,
+    );
+
+    shared
+        .into_clique()
+        .iter()
+        .map(|&sorted_idx| order[sorted_idx])
+        .collect()
+}
+
+pub(crate) fn par_expand(
+    neighbors: &[FixedBitSet],
+    shared: &SharedBest,
+    stop: &Stop,
+    report: &Report<'_>,
+    current: Vec<usize>,
+    mut candidates: FixedBitSet,
+    depth: usize,
+) {
+    if stop.should_stop() {
+        return;
+    }
+    let k_min = (shared.size() + 1).saturating_sub(current.len());
+    let (order, colors) = color_sort(neighbors, &candidates, k_min, &mut ColorScratch::default());
+
+    // 预先生成每个分支的候选集：与串行版本相同，分支时排除颜色更大的节点
+    let mut branches = Vec::with_capacity(order.len());
+    for (&u, &color) in order.iter().zip(&colors).rev() {
+        let mut new_candidates = candidates.clone();
+        new_candidates.intersect_with(&neighbors[u]);
+        branches.push((u, color, new_candidates));
+        candidates.remove(u);
+    }
+
+    branches
+        .into_par_iter()
+        .for_each(|(u, color, new_candidates)| {
+            // 剪枝条件
+            if current.len() + color <= shared.size() || stop.should_stop() {
+                return;
+            }
+
+            let mut clique = current.clone();
+            clique.push(u);
+            if new_candidates.is_clear() {
+                if clique.len() > shared.size() {
+                    shared.offer(&clique);
+                    report(&clique);
+                }
+            } else if depth + 1 < PARALLEL_DEPTH {
+                par_expand(
+                    neighbors,
+                    shared,
+                    stop,
+                    report,
+                    clique,
+                    new_candidates,
+                    depth + 1,
+                );
+            } else {
+                let mut mcs = Mcs::with_shared(neighbors, shared, stop, clique);
+                mcs.report = Some(report);
+                mcs.expand(new_candidates);
+            }
+        });
+}
+
+// 着色用的缓冲区，在所有搜索节点间复用以减少分配
+#[derive(Default)]
+pub(crate) struct ColorScratch {
+    classes: Vec<FixedBitSet>,
+    // 最近一次着色中颜色小于 k_min 的类的数量
+    class_count: usize,
+    uncolored: FixedBitSet,
+    rest: FixedBitSet,
+}
+
+impl ColorScratch {
+    // 最近一次着色中颜色小于 k_min 的节点，按颜色类分组
+    fn low_classes(&self) -> Vec<Vec<usize>> {
+        self.classes[..self.class_count]
+            .iter()
+            .map(|class| class.ones().collect())
+            .collect()
+    }
+}
+
+// 对候选集贪心着色，返回颜色不小于 k_min 的节点及其颜色（颜色从 1 开始，升序）
+// 颜色小于 k_min 的节点一定会被剪掉，留在候选集中即可
+// 按颜色类逐个构造（每次取一个极大独立集），只需位运算
+pub(crate) fn color_sort(
+    neighbors: &[FixedBitSet],
+    candidates: &FixedBitSet,
+    k_min: usize,
+    scratch: &mut ColorScratch,
+) -> (Vec<usize>, Vec<usize>) {
+    let ColorScratch {
+        classes,
+        class_count: low_count,
+        uncolored,
+        rest,
+    } = scratch;
+
+    // 颜色类下标从 0 开始，下标 < low 的类不需要输出
+    let low = k_min.saturating_sub(1);
+    uncolored.clone_from(candidates);
+    let mut class_count = 0;
+
+    while class_count < low && !uncolored.is_clear() {
+        if class_count == classes.len() {
+            classes.push(FixedBitSet::with_capacity(neighbors.len()));
+        }
+        let class = &mut classes[class_count];
+        class.clear();
+        rest.clone_from(uncolored);
+        while let Some(u) = rest.minimum() {
+            rest.difference_with(&neighbors[u]);
+            rest.remove(u);
+            uncolored.remove(u);
+            class.insert(u);
+        }
+        class_count += 1;
+    }
+
+    // 剩下的节点尝试重新着色挤进低颜色类
+    if !uncolored.is_clear() {
+        let leftover: Vec<usize> = uncolored.ones().collect();
+        for u in leftover {
+            if renumber(neighbors, &mut classes[..class_count], u) {
+                uncolored.remove(u);
+            }
+        }
+    }
+
+    *low_count = class_count;
+    let mut order = Vec::with_capacity(uncolored.count_ones(..));
+    let mut colors = Vec::with_capacity(order.capacity());
+    let mut color = class_count;
+    while !uncolored.is_clear() {
+        color += 1;
+        rest.clone_from(uncolored);
+        while let Some(u) = rest.minimum() {
+            rest.difference_with(&neighbors[u]);
+            rest.remove(u);
+            uncolored.remove(u);
+            order.push(u);
+            colors.push(color);
+        }
+    }
+    (order, colors)
+}
+
+// Re-NUMBER：若 u 在某个低颜色类 k1 中只有一个邻居 w，
+// 且 w 能移入另一个低颜色类 k2，则交换后把 u 放进 k1
+fn renumber(neighbors: &[FixedBitSet], classes: &mut [FixedBitSet], u: usize) -> bool {
+    let low = classes.len();
+    for k1 in 0..low {
+        let Some(w) = single_common(&classes[k1], &neighbors[u]) else {
+            continue;
+        };
+        for k2 in (k1 + 1)..low {
+            if classes[k2].is_disjoint(&neighbors[w]) {
+                classes[k1].remove(w);
+                classes[k1].insert(u);
+                classes[k2].insert(w);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// 两个集合恰好只有一个公共元素时返回它，按字比较避免逐位遍历
+fn single_common(a: &FixedBitSet, b: &FixedBitSet) -> Option<usize> {
+    let mut found = None;
+    for (i, (x, y)) in a.as_slice().iter().zip(b.as_slice()).enumerate() {
+        let common = x & y;
+        if common == 0 {
+            continue;
+        }
+        if found.is_some() || common.count_ones() > 1 {
+            return None;
+        }
+        found = Some(i * usize::BITS as usize + common.trailing_zeros() as usize);
+    }
+    found
+}
@@ -0,0 +1,60 @@
+use fixedbitset::FixedBitSet;
+
+use crate::ga::{GAConfig, GenerationStats, find_max_cliques_with_ga_observed_until};
+use crate::graph::{CliqueGraph, Compact, build_neighbors};
+use crate::max_clique::reorder_neighbors;
+use crate::mcs::{Mcs, SharedBest, degeneracy_order};
+use crate::solver::{CancelToken, Stop};
+
+// 组合求解：遗传算法与 MCS 同时运行
+// - 遗传算法在单独的线程中进化，每一代把最优解的规模写入共享下界
+// - MCS 用这个下界剪枝，搜索结束即证明两者中较大的解是最优解
+// - MCS 结束（或到达截止时间、被取消）后通知遗传算法停止
+pub fn find_max_cliques_with_portfolio<G: CliqueGraph>(graph: G) -> Vec<G::NodeId> {
+    let compact = Compact::new(graph);
+    compact.ids(find_max_cliques_with_portfolio_until(
+        &compact.adjacency,
+        &GAConfig::default(),
+        0,
+        &Stop::never(),
+    ))
+}
+
+pub(crate) fn find_max_cliques_with_portfolio_until(
+    adjacency: &[Vec<usize>],
+    config: &GAConfig,
+    lower_bound: usize,
+    stop: &Stop,
+) -> Vec<usize> {
+    let bound = SharedBest::default();
+    bound.raise(lower_bound);
+    let ga_cancel = CancelToken::new();
+    let ga_stop = stop.with_cancel(ga_cancel.clone());
+
+    let (ga_clique, mcs_clique) = std::thread::scope(|scope| {
+        let ga = scope.spawn(|| {
+            let mut observer = |stats: &GenerationStats| bound.raise(stats.best_size);
+            find_max_cliques_with_ga_observed_until(adjacency, config, &ga_stop, &mut observer)
+        });
+
+        let neighbors = build_neighbors(adjacency);
+        let order = degeneracy_order(&neighbors);
+        let sorted_neighbors = reorder_neighbors(&neighbors, &order);
+        let report =
+            |clique: &[usize]| stop.improved(clique.len(), clique.iter().map(|&v| order[v]));
+        let mut mcs = Mcs::with_bound(&sorted_neighbors, &bound, stop, Vec::new());
+        mcs.report = Some(&report);
+        mcs.expand(FixedBitSet::from_iter(0..order.len()));
+        ga_cancel.cancel();
+
+        let mcs_clique: Vec<usize> = mcs.best.iter().map(|&v| order[v]).collect();
+        (ga.join().unwrap(), mcs_clique)
+    });
+
+    // MCS 只记录严格大于下界的解，没找到时遗传算法的解就是最优解
+    if mcs_clique.len() > ga_clique.len() {
+        mcs_clique
+    } else {
+        ga_clique
+    }
+}
@@ -0,0 +1,143 @@
+use petgraph::graph::{NodeIndex, UnGraph};
+
+use crate::graph::{CliqueGraph, Compact, edge_count, induced_adjacency};
+use crate::sparse::core_decomposition;
+
+// 求解前的图约简：规模为 k + 1 的团中每个节点至少有 k 个邻居在团内，
+// 所以核数小于 k 的节点不可能出现在比已知下界 k 更大的团中，可以直接删除。
+// 反复删除度数小于 k 的节点直到不再变化，剩下的恰好是图的 k-核
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Preprocess {
+    // 不做预处理
+    #[default]
+    Off,
+    // 退化序上的贪心团作为下界，只需要邻接表，适合大规模稀疏图
+    Greedy,
+    // 先跑一遍遗传算法作为下界，下界更紧但需要完整的邻接矩阵
+    Genetic,
+}
+
+impl Preprocess {
+    pub fn from_name(name: &str) -> Option<Preprocess> {
+        Some(match name {
+            "off" => Preprocess::Off,
+            "greedy" => Preprocess::Greedy,
+            "genetic" => Preprocess::Genetic,
+            _ => return None,
+        })
+    }
+}
+
+// 约简后的图以及与原图之间的节点映射
+#[derive(Debug, Clone)]
+pub struct Reduction<Id = NodeIndex> {
+    pub graph: UnGraph<(), ()>,
+    // original[i] 为约简图中节点 i 在原图中的 ID
+    pub original: Vec<Id>,
+    // 约简所用的下界（原图 ID）
+    pub lower_bound: Vec<Id>,
+}
+
+impl<Id: Copy> Reduction<Id> {
+    // 把约简图上的团映射回原图 ID
+    pub fn map_back(&self, clique: &[NodeIndex]) -> Vec<Id> {
+        clique.iter().map(|u| self.original[u.index()]).collect()
+    }
+
+    // 约简图上的解与下界中较大的一个（原图 ID）
+    pub fn best_of(&self, clique: &[NodeIndex]) -> Vec<Id> {
+        if clique.len() > self.lower_bound.len() {
+            self.map_back(clique)
+        } else {
+            self.lower_bound.clone()
+        }
+    }
+}
+
+// 每个节点的核数（所在的最大 k-核的 k），按 NodeIndexable::to_index 索引
+pub fn core_numbers<G: CliqueGraph>(graph: G) -> Vec<usize> {
+    let compact = Compact::new(graph);
+    let (_, core) = core_decomposition(&compact.adjacency);
+    let mut numbers = vec![0; graph.node_bound()];
+    for (id, k) in compact.ids(0..core.len()).into_iter().zip(core) {
+        numbers[graph.to_index(id)] = k;
+    }
+    numbers
+}
+
+pub fn greedy_clique<G: CliqueGraph>(graph: G) -> Vec<G::NodeId> {
+    let compact = Compact::new(graph);
+    compact.ids(greedy_clique_in(&compact.adjacency))
+}
+
+// 快速求一个团作为下界：从核数大的节点开始，
+// 在退化序中排在它后面的邻居里按核数从大到小贪心扩展
+pub(crate) fn greedy_clique_in(adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let (order, core) = core_decomposition(adjacency);
+    let mut position = vec![0; order.len()];
+    for (i, &v) in order.iter().enumerate() {
+        position[v] = i;
+    }
+
+    let mut best: Vec<usize> = Vec::new();
+    for &v in order.iter().rev() {
+        // 核数沿退化序不降，后面的节点不可能再给出更大的团
+        if core[v] < best.len() {
+            break;
+        }
+        let mut later: Vec<usize> = adjacency[v]
+            .iter()
+            .copied()
+            .filter(|&u| position[u] > position[v])
+            .collect();
+        later.sort_unstable_by_key(|&u| std::cmp::Reverse(core[u]));
+
+        let mut clique = vec![v];
+        for u in later {
+            if clique.iter().all(|w| adjacency[u].binary_search(w).is_ok()) {
+                clique.push(u);
+            }
+        }
+        if clique.len() > best.len() {
+            best = clique;
+        }
+    }
+    best
+}
+
+// 删除所有不可能出现在比 lower_bound 更大的团中的节点
+pub fn reduce<G: CliqueGraph>(graph: G, lower_bound: Vec<G::NodeId>) -> Reduction<G::NodeId> {
+    let compact = Compact::new(graph);
+    let (adjacency, original) = reduce_adjacency(&compact.adjacency, lower_bound.len());
+
+    let mut reduced = UnGraph::with_capacity(adjacency.len(), edge_count(&adjacency));
+    for _ in 0..adjacency.len() {
+        reduced.add_node(());
+    }
+    for (u, neighbors) in adjacency.iter().enumerate() {
+        for &v in neighbors.iter().filter(|&&v| v > u) {
+            reduced.add_edge(NodeIndex::new(u), NodeIndex::new(v), ());
+        }
+    }
+
+    Reduction {
+        graph: reduced,
+        original: compact.ids(original),
+        lower_bound,
+    }
+}
+
+// 保留核数不小于 k 的节点，返回约简后的邻接表以及 新编号 -> 原编号 的映射
+pub(crate) fn reduce_adjacency(
+    adjacency: &[Vec<usize>],
+    k: usize,
+) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let (_, core) = core_decomposition(adjacency);
+    let original: Vec<usize> = (0..adjacency.len()).filter(|&v| core[v] >= k).collect();
+    (induced_adjacency(adjacency, &original), original)
+}
+
+// 以贪心团为下界约简
+pub fn reduce_with_greedy<G: CliqueGraph>(graph: G) -> Reduction<G::NodeId> {
+    reduce(graph, greedy_clique(graph))
+}
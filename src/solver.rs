@@ -0,0 +1,410 @@
+use petgraph::graph::NodeIndex;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::ga::{GAConfig, find_max_cliques_with_ga_until};
+use crate::graph::{CliqueGraph, Compact, edge_count};
+use crate::max_clique::{Algorithm, find_max_cliques_with_bk_until};
+use crate::mcs::{find_max_cliques_with_mcs_until, find_max_cliques_with_parallel_mcs_until};
+use crate::portfolio::find_max_cliques_with_portfolio_until;
+use crate::preprocess::{Preprocess, greedy_clique_in, reduce_adjacency};
+use crate::sparse::{core_decomposition, find_max_cliques_with_sparse_until};
+
+// 外部取消标记，可以克隆后交给其它线程
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// 求解过程中的进展：找到更大的团或证明了最优性时产生
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    // 目前为止的最优团，节点为 NodeIndexable::to_index 的编号
+    pub clique: Vec<usize>,
+    // 已证明的团规模上界
+    pub upper_bound: usize,
+    pub elapsed: Duration,
+}
+
+// 进度回调，在求解线程（或 rayon 工作线程）中被调用，调用之间互斥
+#[derive(Clone)]
+pub struct ProgressSink(Arc<dyn Fn(&Progress) + Send + Sync>);
+
+impl ProgressSink {
+    pub fn new(f: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        ProgressSink(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressSink")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SolverOptions {
+    pub algorithm: Algorithm,
+    pub deadline: Option<Instant>,
+    pub cancel: Option<CancelToken>,
+    // 仅在使用遗传算法时生效
+    pub ga: GAConfig,
+    // 求解前按下界删除不可能出现在更大团中的节点
+    pub preprocess: Preprocess,
+    pub progress: Option<ProgressSink>,
+}
+
+impl SolverOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    // 从现在开始计时的时间预算；超出 Instant 表示范围的预算视为不限时
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.deadline = Instant::now().checked_add(limit);
+        self
+    }
+
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    pub fn ga_config(mut self, config: GAConfig) -> Self {
+        self.ga = config;
+        self
+    }
+
+    pub fn preprocess(mut self, preprocess: Preprocess) -> Self {
+        self.preprocess = preprocess;
+        self
+    }
+
+    pub fn on_progress(mut self, f: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(ProgressSink::new(f));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveStatus {
+    // 精确算法跑完，结果已被证明最优
+    Optimal,
+    // 近似算法正常结束，结果未经证明
+    Heuristic,
+    // 到达截止时间，返回目前为止的最优解
+    TimedOut,
+    // 被外部取消，返回目前为止的最优解
+    Cancelled,
+}
+
+impl SolveStatus {
+    pub fn is_optimal(self) -> bool {
+        self == SolveStatus::Optimal
+    }
+
+    // 命令行与 api 输出中使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            SolveStatus::Optimal => "optimal",
+            SolveStatus::Heuristic => "heuristic",
+            SolveStatus::TimedOut => "timed-out",
+            SolveStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Solution<Id = NodeIndex> {
+    pub clique: Vec<Id>,
+    pub status: SolveStatus,
+    // 实际运行的算法（Auto 已被解析）
+    pub algorithm: Algorithm,
+    pub elapsed: Duration,
+    // 团规模的上界：证明最优时为团的规模，否则为退化度 + 1
+    pub upper_bound: Option<usize>,
+}
+
+// 可中断地求解最大团，超时或取消时返回目前为止找到的最优解
+pub fn solve<G: CliqueGraph>(graph: G, options: &SolverOptions) -> Solution<G::NodeId> {
+    let start = Instant::now();
+    let compact = Compact::new(graph);
+    let solution = match &options.progress {
+        // 回调中的节点从压缩编号换成 to_index 编号
+        Some(sink) => {
+            let sink = sink.clone();
+            let index: Vec<usize> = (0..compact.adjacency.len())
+                .map(|u| graph.to_index(compact.id(u)))
+                .collect();
+            let options = options.clone().on_progress(move |progress| {
+                (sink.0)(&Progress {
+                    clique: progress.clique.iter().map(|&u| index[u]).collect(),
+                    upper_bound: progress.upper_bound,
+                    elapsed: progress.elapsed,
+                })
+            });
+            solve_compact(&compact.adjacency, &options)
+        }
+        None => solve_compact(&compact.adjacency, options),
+    };
+    Solution {
+        clique: compact.ids(solution.clique),
+        status: solution.status,
+        algorithm: solution.algorithm,
+        elapsed: start.elapsed(),
+        upper_bound: solution.upper_bound,
+    }
+}
+
+// 在压缩邻接表上求解，结果为内部编号
+pub(crate) fn solve_compact(adjacency: &[Vec<usize>], options: &SolverOptions) -> Solution<usize> {
+    solve_compact_from(adjacency, options, Vec::new())
+}
+
+// initial 为已知的团（热启动），与预处理得到的下界一样用于约简和剪枝
+pub(crate) fn solve_compact_from(
+    adjacency: &[Vec<usize>],
+    options: &SolverOptions,
+    initial: Vec<usize>,
+) -> Solution<usize> {
+    let start = Instant::now();
+    let stop = Stop::new(options);
+    // 退化度 + 1 是团规模的上界
+    let (_, core) = core_decomposition(adjacency);
+    let degeneracy_bound = core.iter().max().map_or(0, |&k| k + 1);
+    stop.bound(degeneracy_bound);
+
+    // 约简后 Auto 按约简图的规模和密度选择算法
+    let lower_bound = match options.preprocess {
+        Preprocess::Off => None,
+        Preprocess::Greedy => Some(greedy_clique_in(adjacency)),
+        Preprocess::Genetic if adjacency.is_empty() => Some(Vec::new()),
+        Preprocess::Genetic => Some(find_max_cliques_with_ga_until(
+            adjacency,
+            &options.ga,
+            &stop,
+        )),
+    };
+    let lower_bound = match lower_bound {
+        Some(bound) if bound.len() >= initial.len() => Some(bound),
+        _ if initial.is_empty() => None,
+        _ => Some(initial),
+    };
+    if let Some(bound) = &lower_bound {
+        stop.improved(bound.len(), bound.iter().copied());
+    }
+    let reduction = lower_bound
+        .as_ref()
+        .map(|bound| reduce_adjacency(adjacency, bound.len()));
+    // 之后的解都在约简图上，回调前映射回原编号
+    if let Some((_, original)) = &reduction {
+        stop.remap(original.clone());
+    }
+    let target = reduction.as_ref().map_or(adjacency, |(reduced, _)| reduced);
+    let algorithm = options
+        .algorithm
+        .resolve_for(target.len(), edge_count(target));
+
+    // 精确算法只需寻找比下界更大的团
+    let floor = lower_bound.as_ref().map_or(0, Vec::len);
+    let clique = match algorithm {
+        _ if target.is_empty() => Vec::new(),
+        Algorithm::BronKerbosch => find_max_cliques_with_bk_until(target, &stop),
+        Algorithm::Mcs => find_max_cliques_with_mcs_until(target, floor, &stop),
+        Algorithm::ParallelMcs => find_max_cliques_with_parallel_mcs_until(target, floor, &stop),
+        Algorithm::Sparse => find_max_cliques_with_sparse_until(target, floor, &stop),
+        Algorithm::Portfolio => {
+            find_max_cliques_with_portfolio_until(target, &options.ga, floor, &stop)
+        }
+        _ => find_max_cliques_with_ga_until(target, &options.ga, &stop),
+    };
+
+    // 约简图为空说明下界已经是最优解
+    let finished = if algorithm.is_exact() || target.is_empty() {
+        SolveStatus::Optimal
+    } else {
+        SolveStatus::Heuristic
+    };
+    // 约简图上的解与下界中较大的一个，映射回原编号
+    let clique = match (lower_bound, &reduction) {
+        (Some(bound), _) if clique.len() <= bound.len() => bound,
+        (_, Some((_, original))) => clique.into_iter().map(|u| original[u]).collect(),
+        _ => clique,
+    };
+    let status = stop.status(finished);
+    let upper_bound = if status.is_optimal() {
+        stop.proved(&clique);
+        clique.len()
+    } else {
+        degeneracy_bound
+    };
+    Solution {
+        clique,
+        status,
+        algorithm,
+        elapsed: start.elapsed(),
+        upper_bound: Some(upper_bound),
+    }
+}
+
+const RUNNING: u8 = 0;
+const TIMED_OUT: u8 = 1;
+const CANCELLED: u8 = 2;
+
+// 求解器内部的停止检查，触发后记住原因，之后的检查都直接返回
+pub(crate) struct Stop {
+    deadline: Option<Instant>,
+    cancel: Option<CancelToken>,
+    reason: AtomicU8,
+    reporter: Option<Arc<Reporter>>,
+}
+
+// 记录已报告的最优解规模，只在解变大时调用回调
+struct Reporter {
+    sink: ProgressSink,
+    start: Instant,
+    best: AtomicUsize,
+    state: Mutex<ReportState>,
+}
+
+struct ReportState {
+    upper_bound: usize,
+    // 算法的输入是约简图时，约简图编号 -> 压缩编号
+    original: Option<Vec<usize>>,
+}
+
+impl Reporter {
+    fn emit(&self, state: &ReportState, clique: Vec<usize>) {
+        (self.sink.0)(&Progress {
+            clique,
+            upper_bound: state.upper_bound,
+            elapsed: self.start.elapsed(),
+        });
+    }
+}
+
+impl Stop {
+    pub(crate) fn new(options: &SolverOptions) -> Self {
+        Stop {
+            deadline: options.deadline,
+            cancel: options.cancel.clone(),
+            reason: AtomicU8::new(RUNNING),
+            reporter: options.progress.clone().map(|sink| {
+                Arc::new(Reporter {
+                    sink,
+                    start: Instant::now(),
+                    best: AtomicUsize::new(0),
+                    state: Mutex::new(ReportState {
+                        upper_bound: usize::MAX,
+                        original: None,
+                    }),
+                })
+            }),
+        }
+    }
+
+    pub(crate) fn never() -> Self {
+        Self::new(&SolverOptions::default())
+    }
+
+    // 截止时间相同，但改由 cancel 控制取消的新实例，触发原因不会影响原实例
+    pub(crate) fn with_cancel(&self, cancel: CancelToken) -> Self {
+        Stop {
+            deadline: self.deadline,
+            cancel: Some(cancel),
+            reason: AtomicU8::new(RUNNING),
+            reporter: self.reporter.clone(),
+        }
+    }
+
+    pub(crate) fn should_stop(&self) -> bool {
+        if self.reason.load(Ordering::Relaxed) != RUNNING {
+            return true;
+        }
+        let reason = if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            CANCELLED
+        } else if self.deadline.is_some_and(|d| Instant::now() >= d) {
+            TIMED_OUT
+        } else {
+            return false;
+        };
+        self.reason.store(reason, Ordering::Relaxed);
+        true
+    }
+
+    // 算法找到规模为 size 的团时调用，clique 为算法输入的编号，只在需要回调时才会被遍历
+    pub(crate) fn improved(&self, size: usize, clique: impl IntoIterator<Item = usize>) {
+        let Some(reporter) = &self.reporter else {
+            return;
+        };
+        if size <= reporter.best.load(Ordering::Relaxed) {
+            return;
+        }
+        let state = reporter.state.lock().unwrap();
+        if size <= reporter.best.load(Ordering::Relaxed) {
+            return;
+        }
+        reporter.best.store(size, Ordering::Relaxed);
+        let clique = match &state.original {
+            Some(original) => clique.into_iter().map(|u| original[u]).collect(),
+            None => clique.into_iter().collect(),
+        };
+        reporter.emit(&state, clique);
+    }
+
+    pub(crate) fn bound(&self, upper_bound: usize) {
+        if let Some(reporter) = &self.reporter {
+            let mut state = reporter.state.lock().unwrap();
+            state.upper_bound = state.upper_bound.min(upper_bound);
+        }
+    }
+
+    pub(crate) fn remap(&self, original: Vec<usize>) {
+        if let Some(reporter) = &self.reporter {
+            reporter.state.lock().unwrap().original = Some(original);
+        }
+    }
+
+    // 证明 clique（压缩编号）是最大团，上界收紧到它的规模
+    pub(crate) fn proved(&self, clique: &[usize]) {
+        if let Some(reporter) = &self.reporter {
+            let mut state = reporter.state.lock().unwrap();
+            state.upper_bound = clique.len();
+            reporter.best.fetch_max(clique.len(), Ordering::Relaxed);
+            reporter.emit(&state, clique.to_vec());
+        }
+    }
+
+    // 没有被打断时返回 finished
+    pub(crate) fn status(&self, finished: SolveStatus) -> SolveStatus {
+        match self.reason.load(Ordering::Relaxed) {
+            TIMED_OUT => SolveStatus::TimedOut,
+            CANCELLED => SolveStatus::Cancelled,
+            _ => finished,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use fixedbitset::FixedBitSet;
+use rayon::prelude::*;
+
+use crate::graph::{CliqueGraph, Compact};
+use crate::max_clique::reorder_neighbors;
+use crate::mcs::{Mcs, SharedBest, degeneracy_order};
+use crate::solver::Stop;
+
+// 面向大规模稀疏图的精确算法，不构建 n×n 的邻接矩阵
+// 1. 核分解得到退化序与每个节点的核数
+// 2. 对每个节点 v，只在排在它后面的邻居（不超过退化度个）上建局部位图，用 MCS 求解
+// 3. 核数 + 1 不超过当前最优解的节点不可能出现在更大的团中，直接剪掉
+pub fn find_max_cliques_with_sparse<G: CliqueGraph>(graph: G) -> Vec<G::NodeId> {
+    let compact = Compact::new(graph);
+    compact.ids(find_max_cliques_with_sparse_until(
+        &compact.adjacency,
+        0,
+        &Stop::never(),
+    ))
+}
+
+pub(crate) fn find_max_cliques_with_sparse_until(
+    adjacency: &[Vec<usize>],
+    lower_bound: usize,
+    stop: &Stop,
+) -> Vec<usize> {
+    let (order, core) = core_decomposition(adjacency);
+    let mut position = vec![0; order.len()];
+    for (i, &v) in order.iter().enumerate() {
+        position[v] = i;
+    }
+
+    let shared = SharedBest::default();
+    shared.raise(lower_bound);
+    // 核数大的节点排在后面，先处理它们更容易尽早找到大团
+    order.par_iter().rev().for_each(|&v| {
+        let best = shared.size();
+        if stop.should_stop() || core[v] < best {
+            return;
+        }
+        let later: Vec<usize> = adjacency[v]
+            .iter()
+            .copied()
+            .filter(|&u| position[u] > position[v] && core[u] >= best)
+            .collect();
+        if later.len() < best {
+            return;
+        }
+        if later.is_empty() {
+            shared.offer(&[v]);
+            stop.improved(1, [v]);
+            return;
+        }
+
+        let local = local_neighbors(adjacency, &later);
+        let local_order = degeneracy_order(&local);
+        let sorted_local = reorder_neighbors(&local, &local_order);
+
+        // current 中放入全局编号的 v，局部团的规模与全局最优解直接可比
+        let report = |clique: &[usize]| {
+            let rest = clique[1..].iter().map(|&i| later[local_order[i]]);
+            stop.improved(clique.len(), std::iter::once(v).chain(rest));
+        };
+        let mut mcs = Mcs::with_bound(&sorted_local, &shared, stop, vec![v]);
+        mcs.report = Some(&report);
+        mcs.expand(FixedBitSet::from_iter(0..later.len()));
+        if let Some((&v, rest)) = mcs.best.split_first() {
+            let mut clique = vec![v];
+            clique.extend(rest.iter().map(|&i| later[local_order[i]]));
+            shared.offer(&clique);
+        }
+    });
+
+    shared.into_clique()
+}
+
+// 核分解（Batagelj-Zaversnik 桶算法，O(n + m)）
+// 返回节点的删除顺序（核数不降）以及每个节点的核数
+pub(crate) fn core_decomposition(adjacency: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let n = adjacency.len();
+    let mut degree: Vec<usize> = adjacency.iter().map(Vec::len).collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+
+    // 按度数计数排序：bin[d] 为度数为 d 的节点在 order 中的起始位置
+    let mut bin = vec![0; max_degree + 1];
+    for &d in &degree {
+        bin[d] += 1;
+    }
+    let mut start = 0;
+    for count in bin.iter_mut() {
+        let c = *count;
+        *count = start;
+        start += c;
+    }
+    let mut order = vec![0; n];
+    let mut position = vec![0; n];
+    for v in 0..n {
+        position[v] = bin[degree[v]];
+        order[position[v]] = v;
+        bin[degree[v]] += 1;
+    }
+    for d in (1..=max_degree).rev() {
+        bin[d] = bin[d - 1];
+    }
+    bin[0] = 0;
+
+    // 依次删除度数最小的节点，邻居的度数减一并移到前一个桶的末尾
+    for i in 0..n {
+        let v = order[i];
+        for &u in &adjacency[v] {
+            if degree[u] > degree[v] {
+                let du = degree[u];
+                let pu = position[u];
+                let pw = bin[du];
+                let w = order[pw];
+                if u != w {
+                    order.swap(pu, pw);
+                    position[u] = pw;
+                    position[w] = pu;
+                }
+                bin[du] += 1;
+                degree[u] -= 1;
+            }
+        }
+    }
+    (order, degree)
+}
+
+// nodes 诱导子图的局部邻接位图，nodes 需按编号升序排列
+fn local_neighbors(adjacency: &[Vec<usize>], nodes: &[usize]) -> Vec<FixedBitSet> {
+    let k = nodes.len();
+    let mut local = vec![FixedBitSet::with_capacity(k); k];
+    for (i, &u) in nodes.iter().enumerate() {
+        // 两个有序列表求交
+        let (mut a, mut b) = (0, 0);
+        let neighbors = &adjacency[u];
+        while a < neighbors.len() && b < k {
+            match neighbors[a].cmp(&nodes[b]) {
+                std::cmp::Ordering::Less => a += 1,
+                std::cmp::Ordering::Greater => b += 1,
+                std::cmp::Ordering::Equal => {
+                    local[i].insert(b);
+                    a += 1;
+                    b += 1;
+                }
+            }
+        }
+    }
+    local
+}
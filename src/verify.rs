@@ -0,0 +1,242 @@
+use fixedbitset::FixedBitSet;
+use petgraph::graph::{NodeIndex, UnGraph};
+use std::io::{self, BufRead, Write};
+use thiserror::Error;
+
+use crate::graph::{CliqueGraph, Compact, build_neighbors};
+
+// Id 为调用方的节点 ID 类型，出错的节点按 Debug 格式输出
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum VerifyError<Id = NodeIndex> {
+    #[error("node {0:?} is not in the graph")]
+    NodeOutOfRange(Id),
+    #[error("node {0:?} appears more than once")]
+    DuplicateNode(Id),
+    #[error("nodes {0:?} and {1:?} are not adjacent")]
+    NotAdjacent(Id, Id),
+    #[error("node {0:?} can be added to the clique")]
+    NotMaximal(Id),
+    #[error("invalid proof: {0}")]
+    InvalidProof(String),
+}
+
+// 检查给定节点集合是否构成团，失败时给出不相邻的节点对
+// 有向图按无向图处理，任一方向有边即视为相邻
+pub fn check_clique<G: CliqueGraph>(
+    graph: G,
+    clique: &[G::NodeId],
+) -> Result<(), VerifyError<G::NodeId>> {
+    let mut present = FixedBitSet::with_capacity(graph.node_bound());
+    present.extend(graph.node_identifiers().map(|u| graph.to_index(u)));
+    let mut seen = FixedBitSet::with_capacity(graph.node_bound());
+    for &u in clique {
+        if !present.contains(graph.to_index(u)) {
+            return Err(VerifyError::NodeOutOfRange(u));
+        }
+        if seen.put(graph.to_index(u)) {
+            return Err(VerifyError::DuplicateNode(u));
+        }
+    }
+
+    let neighbors: Vec<Vec<usize>> = clique
+        .iter()
+        .map(|&u| {
+            let mut neighbors: Vec<usize> = graph.neighbors(u).map(|v| graph.to_index(v)).collect();
+            neighbors.sort_unstable();
+            neighbors
+        })
+        .collect();
+    let adjacent = |i: usize, j: usize| {
+        neighbors[i]
+            .binary_search(&graph.to_index(clique[j]))
+            .is_ok()
+            || neighbors[j]
+                .binary_search(&graph.to_index(clique[i]))
+                .is_ok()
+    };
+    for i in 0..clique.len() {
+        for j in i + 1..clique.len() {
+            if !adjacent(i, j) {
+                return Err(VerifyError::NotAdjacent(clique[i], clique[j]));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 检查团是否极大，失败时给出一个还能加入的节点
+pub fn check_maximal<G: CliqueGraph>(
+    graph: G,
+    clique: &[G::NodeId],
+) -> Result<(), VerifyError<G::NodeId>> {
+    check_clique(graph, clique)?;
+    let compact = Compact::new(graph);
+    let neighbors = build_neighbors(&compact.adjacency);
+    let mut extension = FixedBitSet::with_capacity(neighbors.len());
+    extension.insert_range(..);
+    for &u in clique {
+        // check_clique 已保证节点都在图中
+        extension.intersect_with(&neighbors[compact.index(u).unwrap()]);
+    }
+    match extension.minimum() {
+        Some(v) => Err(VerifyError::NotMaximal(compact.id(v))),
+        None => Ok(()),
+    }
+}
+
+// 精确算法的搜索树记录，可以独立重放以证明不存在更大的团
+// 每个搜索节点（先序）对应一个 ProofStep，节点的候选集 P 被分成两部分：
+// - classes：若干个独立集（着色），它们的并集 S 中的团规模不超过颜色数，直接剪掉
+// - branch：分支节点 b_1..b_m，从 b_m 倒序分支，b_j 的子节点候选集为 (S ∪ {b_1..b_j-1}) ∩ N(b_j)
+// 子节点候选集为空时没有对应的 ProofStep
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Proof {
+    pub steps: Vec<ProofStep>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProofStep {
+    pub classes: Vec<Vec<usize>>,
+    pub branch: Vec<usize>,
+}
+
+impl Proof {
+    // 文本格式：每个颜色类一行 `k v1 v2 ...`，每个搜索节点以 `b v1 v2 ...` 结束，`c` 开头为注释
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "c clique proof, {} steps", self.steps.len())?;
+        for step in &self.steps {
+            for class in &step.classes {
+                write_line(&mut writer, 'k', class)?;
+            }
+            write_line(&mut writer, 'b', &step.branch)?;
+        }
+        writer.flush()
+    }
+
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<Proof> {
+        let mut proof = Proof::default();
+        let mut step = ProofStep::default();
+        for (line_no, line) in reader.lines().enumerate() {
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            let invalid =
+                || io::Error::new(io::ErrorKind::InvalidData, format!("line {}", line_no + 1));
+            let kind = parts.next();
+            if matches!(kind, None | Some("c")) {
+                continue;
+            }
+            let nodes = parts
+                .map(|s| s.parse::<usize>().map_err(|_| invalid()))
+                .collect::<Result<Vec<_>, _>>()?;
+            match kind {
+                Some("k") => step.classes.push(nodes),
+                Some("b") => {
+                    step.branch = nodes;
+                    proof.steps.push(std::mem::take(&mut step));
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        if !step.classes.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unfinished step",
+            ));
+        }
+        Ok(proof)
+    }
+}
+
+fn write_line<W: Write>(writer: &mut W, kind: char, nodes: &[usize]) -> io::Result<()> {
+    write!(writer, "{kind}")?;
+    for v in nodes {
+        write!(writer, " {v}")?;
+    }
+    writeln!(writer)
+}
+
+// 检查团是最大团：团本身合法，且证明表明不存在规模更大的团
+pub fn check_maximum<N, E>(
+    graph: &UnGraph<N, E>,
+    clique: &[NodeIndex],
+    proof: &Proof,
+) -> Result<(), VerifyError> {
+    check_clique(graph, clique)?;
+    let mut checker = ProofChecker {
+        neighbors: build_neighbors(&Compact::new(graph).adjacency),
+        claimed: clique.len(),
+        steps: proof.steps.iter(),
+    };
+    let mut all = FixedBitSet::with_capacity(graph.node_count());
+    all.insert_range(..);
+    if !all.is_clear() {
+        checker.check_node(0, all)?;
+    }
+    if checker.steps.next().is_some() {
+        return Err(invalid_proof("unused steps at the end"));
+    }
+    Ok(())
+}
+
+fn invalid_proof(msg: &str) -> VerifyError {
+    VerifyError::InvalidProof(msg.into())
+}
+
+struct ProofChecker<'p> {
+    neighbors: Vec<FixedBitSet>,
+    claimed: usize,
+    steps: std::slice::Iter<'p, ProofStep>,
+}
+
+impl ProofChecker<'_> {
+    // depth 为当前团的规模，需要证明 candidates 中不存在规模大于 claimed - depth 的团
+    fn check_node(&mut self, depth: usize, candidates: FixedBitSet) -> Result<(), VerifyError> {
+        let step = self
+            .steps
+            .next()
+            .ok_or_else(|| invalid_proof("missing step"))?;
+        if depth + step.classes.len() > self.claimed {
+            return Err(invalid_proof("too many color classes"));
+        }
+
+        let node_count = self.neighbors.len();
+        let mut covered = FixedBitSet::with_capacity(node_count);
+        let mut cover = |v: usize| {
+            if v >= node_count || covered.put(v) {
+                return Err(invalid_proof("node out of range or repeated"));
+            }
+            Ok(())
+        };
+        for class in &step.classes {
+            for (i, &u) in class.iter().enumerate() {
+                cover(u)?;
+                if class[i + 1..]
+                    .iter()
+                    .any(|&v| self.neighbors[u].contains(v))
+                {
+                    return Err(invalid_proof("color class is not an independent set"));
+                }
+            }
+        }
+        for &b in &step.branch {
+            cover(b)?;
+        }
+        if covered != candidates {
+            return Err(invalid_proof("step does not match the candidate set"));
+        }
+
+        // 倒序分支，每处理完一个分支节点就把它从候选集中移除
+        let mut remaining = candidates;
+        for &b in step.branch.iter().rev() {
+            remaining.remove(b);
+            let mut child = remaining.clone();
+            child.intersect_with(&self.neighbors[b]);
+            if !child.is_clear() {
+                self.check_node(depth + 1, child)?;
+            } else if depth + 1 > self.claimed {
+                return Err(invalid_proof("branch reaches a larger clique"));
+            }
+        }
+        Ok(())
+    }
+}
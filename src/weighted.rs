@@ -0,0 +1,141 @@
+use bitvec::prelude::*;
+use fixedbitset::FixedBitSet;
+use petgraph::graph::{NodeIndex, UnGraph};
+use std::iter::Sum;
+use std::ops::Add;
+
+use crate::ga::{Fitness, GAConfig, GeneticAlgorithm, build_adj_matrix};
+use crate::graph::Compact;
+use crate::max_clique::{build_sorted_neighbors, prefer_bk};
+
+// 节点权重，Default 视为零
+pub trait Weight: Copy + PartialOrd + Default + Add<Output = Self> + Sum + Send + Sync {}
+
+impl<T> Weight for T where T: Copy + PartialOrd + Default + Add<Output = T> + Sum + Send + Sync {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedClique<W> {
+    pub nodes: Vec<NodeIndex>,
+    pub weight: W,
+}
+
+// 求节点权重之和最大的团，权重取自节点数据
+// 非正权重的节点不会出现在结果中，边的权重被忽略
+pub fn find_max_weight_clique<W: Weight, E>(graph: &UnGraph<W, E>) -> WeightedClique<W> {
+    if prefer_bk(graph.node_count(), graph.edge_count()) {
+        return find_max_weight_clique_exact(graph);
+    }
+    find_max_weight_clique_with_ga(graph)
+}
+
+// 分支限界精确求解
+pub fn find_max_weight_clique_exact<W: Weight, E>(graph: &UnGraph<W, E>) -> WeightedClique<W> {
+    let node_count = graph.node_count();
+    let (sorted_neighbors, sorted_nodes) = build_sorted_neighbors(&Compact::new(graph).adjacency);
+    let weights: Vec<W> = sorted_nodes
+        .iter()
+        .map(|&old_idx| graph[NodeIndex::new(old_idx)])
+        .collect();
+
+    // 非正权重的节点只会拉低团的权重
+    let zero = W::default();
+    let candidates: FixedBitSet = (0..node_count).filter(|&u| weights[u] > zero).collect();
+
+    let mut best = FixedBitSet::with_capacity(node_count);
+    let mut best_weight = zero;
+    expand(
+        &sorted_neighbors,
+        &weights,
+        &mut FixedBitSet::with_capacity(node_count),
+        zero,
+        candidates,
+        &mut best,
+        &mut best_weight,
+    );
+
+    WeightedClique {
+        nodes: best
+            .ones()
+            .map(|sorted_idx| NodeIndex::new(sorted_nodes[sorted_idx]))
+            .collect(),
+        weight: best_weight,
+    }
+}
+
+fn expand<W: Weight>(
+    neighbors: &[FixedBitSet],
+    weights: &[W],
+    current_clique: &mut FixedBitSet,
+    current_weight: W,
+    mut candidates: FixedBitSet,
+    best: &mut FixedBitSet,
+    best_weight: &mut W,
+) {
+    if current_weight > *best_weight {
+        best.clone_from(current_clique);
+        *best_weight = current_weight;
+    }
+
+    while let Some(u) = candidates.ones().next() {
+        // 剪枝条件：把剩余候选全部加入也无法超过当前最优
+        let bound = current_weight + candidates.ones().map(|v| weights[v]).sum();
+        if bound <= *best_weight {
+            return;
+        }
+
+        let mut new_candidates = candidates.clone();
+        new_candidates.intersect_with(&neighbors[u]);
+
+        current_clique.insert(u);
+        expand(
+            neighbors,
+            weights,
+            current_clique,
+            current_weight + weights[u],
+            new_candidates,
+            best,
+            best_weight,
+        );
+
+        // 回溯
+        current_clique.remove(u);
+        candidates.remove(u);
+    }
+}
+
+// 以权重和为适应度的遗传算法近似求解
+pub fn find_max_weight_clique_with_ga<W: Weight, E>(graph: &UnGraph<W, E>) -> WeightedClique<W> {
+    if graph.node_count() == 0 {
+        return WeightedClique {
+            nodes: Vec::new(),
+            weight: W::default(),
+        };
+    }
+
+    let adj_matrix = build_adj_matrix(&Compact::new(graph).adjacency);
+    let weights: Vec<W> = graph.node_weights().copied().collect();
+    let mut ga =
+        GeneticAlgorithm::with_fitness(&adj_matrix, GAConfig::default(), WeightSum(&weights));
+    ga.run();
+
+    // 团的子集仍是团，去掉非正权重的节点
+    let zero = W::default();
+    let nodes: Vec<NodeIndex> = ga
+        .best_clique()
+        .iter_ones()
+        .filter(|&u| weights[u] > zero)
+        .map(NodeIndex::new)
+        .collect();
+    let weight = nodes.iter().map(|n| weights[n.index()]).sum();
+    WeightedClique { nodes, weight }
+}
+
+struct WeightSum<'w, W>(&'w [W]);
+
+impl<W: Weight> Fitness for WeightSum<'_, W> {
+    type Value = W;
+
+    fn fitness(&self, clique: &BitSlice) -> W {
+        clique.iter_ones().map(|u| self.0[u]).sum()
+    }
+}
@@ -0,0 +1,524 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use serde_json::{Value, json};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::process::{Child, Command, Stdio};
+    use std::thread::sleep;
+    use std::time::{Duration, Instant};
+
+    // 在随机端口上启动 api 服务，测试结束时结束进程
+    struct Server {
+        child: Child,
+        addr: String,
+    }
+
+    impl Server {
+        fn start(workers: usize) -> Server {
+            Server::start_with(workers, &[])
+        }
+
+        fn start_with(workers: usize, env: &[(&str, &str)]) -> Server {
+            let mut child = Command::new(env!("CARGO_BIN_EXE_api"))
+                .env("CLIQUE_API_ADDR", "127.0.0.1:0")
+                .env("CLIQUE_WORKERS", workers.to_string())
+                .envs(env.iter().copied())
+                .stderr(Stdio::piped())
+                .spawn()
+                .unwrap();
+            let mut stderr = BufReader::new(child.stderr.take().unwrap());
+            let mut line = String::new();
+            stderr.read_line(&mut line).unwrap();
+            let addr = line
+                .trim()
+                .strip_prefix("listening on ")
+                .unwrap()
+                .to_string();
+            Server { child, addr }
+        }
+
+        fn request(&self, method: &str, path: &str, body: Option<&Value>) -> (u16, Value) {
+            let body = body.map(Value::to_string).unwrap_or_default();
+            self.send(method, path, body.as_bytes())
+        }
+
+        fn send(&self, method: &str, path: &str, body: &[u8]) -> (u16, Value) {
+            let mut stream = TcpStream::connect(&self.addr).unwrap();
+            write!(
+                stream,
+                "{method} {path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\
+                 Content-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                self.addr,
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(body).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            let (head, body) = response.split_once("\r\n\r\n").unwrap();
+            let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+            let body = if body.is_empty() {
+                Value::Null
+            } else {
+                serde_json::from_str(body).unwrap()
+            };
+            (status, body)
+        }
+
+        // 上传图文件并等待求解结束
+        fn solve_file(&self, query: &str, body: &[u8]) -> Value {
+            let (status, job) = self.send("POST", &format!("/api/jobs/upload{query}"), body);
+            assert_eq!(status, 202, "{job}");
+            self.wait(job["id"].as_u64().unwrap())
+        }
+
+        // 读取 SSE 流直到服务端关闭，每个块恰好是一个事件，跳过分块长度与注释行
+        fn events(&self, path: &str) -> Vec<(String, Value)> {
+            let mut stream = TcpStream::connect(&self.addr).unwrap();
+            write!(
+                stream,
+                "GET {path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+                self.addr
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            assert!(response.contains("text/event-stream"), "{response}");
+            let mut events = Vec::new();
+            let mut name = String::new();
+            for line in response.lines() {
+                if let Some(event) = line.strip_prefix("event: ") {
+                    name = event.to_string();
+                } else if let Some(data) = line.strip_prefix("data: ") {
+                    events.push((name.clone(), serde_json::from_str(data).unwrap()));
+                }
+            }
+            events
+        }
+
+        // 轮询直到任务结束
+        fn wait(&self, id: u64) -> Value {
+            let start = Instant::now();
+            loop {
+                let (status, job) = self.request("GET", &format!("/api/jobs/{id}"), None);
+                assert_eq!(status, 200);
+                if job["status"] != "queued" && job["status"] != "running" {
+                    return job;
+                }
+                assert!(start.elapsed() < Duration::from_secs(60), "{job}");
+                sleep(Duration::from_millis(20));
+            }
+        }
+    }
+
+    impl Drop for Server {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    // DIMACS 文件的边，节点编号从 1 开始
+    fn edges(path: &str) -> Value {
+        let graph = read_dimacs(path).unwrap();
+        let edges: Vec<(usize, usize)> = graph
+            .raw_edges()
+            .iter()
+            .map(|e| (e.source().index() + 1, e.target().index() + 1))
+            .collect();
+        json!({ "edges": edges })
+    }
+
+    #[test]
+    fn test_job_lifecycle() {
+        let server = Server::start(2);
+        let (status, job) =
+            server.request("POST", "/api/jobs", Some(&edges("data/brock200_4.clq")));
+        assert_eq!(status, 202);
+        let id = job["id"].as_u64().unwrap();
+
+        let job = server.wait(id);
+        assert_eq!(job["status"], "done");
+        assert_eq!(job["result"]["size"], 17);
+        assert_eq!(job["result"]["status"], "optimal");
+        let clique = job["result"]["max_clique"].as_array().unwrap();
+        assert_eq!(clique.len(), 17);
+        assert!(
+            clique
+                .iter()
+                .all(|u| (1..=200).contains(&u.as_u64().unwrap()))
+        );
+
+        // 删除已结束的任务
+        let path = format!("/api/jobs/{id}");
+        assert_eq!(server.request("DELETE", &path, None).0, 204);
+        assert_eq!(server.request("GET", &path, None).0, 404);
+        assert_eq!(server.request("DELETE", &path, None).0, 404);
+    }
+
+    #[test]
+    fn test_cancel_running_and_queued() {
+        // 单个工作线程：第一个任务运行时第二个任务排队
+        let server = Server::start(1);
+        let hard = edges("data/C250.9.clq");
+        let (_, running) = server.request("POST", "/api/jobs", Some(&hard));
+        let (_, queued) = server.request("POST", "/api/jobs", Some(&hard));
+        let (running, queued) = (
+            running["id"].as_u64().unwrap(),
+            queued["id"].as_u64().unwrap(),
+        );
+        assert_eq!(
+            server
+                .request("GET", &format!("/api/jobs/{queued}"), None)
+                .1["status"],
+            "queued"
+        );
+
+        let (status, job) = server.request("DELETE", &format!("/api/jobs/{queued}"), None);
+        assert_eq!(status, 200);
+        assert_eq!(job["status"], "cancelled");
+        assert_eq!(job["result"], Value::Null);
+
+        assert_eq!(
+            server
+                .request("DELETE", &format!("/api/jobs/{running}"), None)
+                .0,
+            200
+        );
+        let job = server.wait(running);
+        assert_eq!(job["status"], "cancelled");
+        assert_eq!(job["result"]["status"], "cancelled");
+        assert!(job["result"]["size"].as_u64().unwrap() > 0);
+
+        // 工作线程空出后可以继续处理新任务
+        let (_, job) = server.request("POST", "/api/jobs", Some(&edges("data/small.clq")));
+        let job = server.wait(job["id"].as_u64().unwrap());
+        assert_eq!(job["result"]["size"], 3);
+    }
+
+    #[test]
+    fn test_job_events() {
+        let server = Server::start(1);
+        let (_, job) = server.request("POST", "/api/jobs", Some(&edges("data/brock200_2.clq")));
+        let id = job["id"].as_u64().unwrap();
+
+        let events = server.events(&format!("/api/jobs/{id}/events"));
+        let (name, last) = events.last().unwrap();
+        assert_eq!(name, "state");
+        assert_eq!(last["status"], "done");
+        assert_eq!(last["result"]["size"], 12);
+        assert_eq!(last["progress"]["upper_bound"], 12);
+
+        // 进度中的团规模不降，上界不升，最后一次进度证明了最优解
+        let progress: Vec<&Value> = events
+            .iter()
+            .filter(|(name, _)| name == "progress")
+            .map(|(_, data)| data)
+            .collect();
+        assert!(!progress.is_empty());
+        assert!(
+            progress
+                .windows(2)
+                .all(|w| w[0]["size"].as_u64() <= w[1]["size"].as_u64()
+                    && w[0]["upper_bound"].as_u64() >= w[1]["upper_bound"].as_u64())
+        );
+        let best = progress.last().unwrap();
+        assert_eq!(best["size"], 12);
+        assert_eq!(best["upper_bound"], 12);
+        assert_eq!(best["clique"], last["result"]["max_clique"]);
+
+        assert_eq!(server.request("GET", "/api/jobs/999/events", None).0, 404);
+    }
+
+    #[test]
+    fn test_upload_files() {
+        let server = Server::start(2);
+        let dimacs = std::fs::read("data/brock200_4.clq").unwrap();
+        let job = server.solve_file("", &dimacs);
+        assert_eq!(job["result"]["size"], 17);
+        assert!(
+            job["result"]["max_clique"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(Value::is_u64)
+        );
+
+        // 压缩文件按魔数解压
+        let mut gz = GzEncoder::new(Vec::new(), Compression::fast());
+        gz.write_all(&dimacs).unwrap();
+        let job = server.solve_file("?name=brock200_4.clq.gz", &gz.finish().unwrap());
+        assert_eq!(job["result"]["size"], 17);
+
+        // p 行声明的孤立节点同样保留
+        let job = server.solve_file("", b"p edge 3 0\n");
+        assert_eq!(job["result"]["size"], 1);
+
+        // p 行声明的边数不准时照常求解
+        let job = server.solve_file("", &std::fs::read("data/small.clq").unwrap());
+        assert_eq!(job["result"]["size"], 3);
+
+        // 非数字的节点 ID 以字符串返回
+        let edges = b"# comment\nalice bob\nbob carol\ncarol alice\ncarol dave\n";
+        let job = server.solve_file("?format=edges", edges);
+        let mut clique: Vec<&str> = job["result"]["max_clique"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|u| u.as_str().unwrap())
+            .collect();
+        clique.sort();
+        assert_eq!(clique, ["alice", "bob", "carol"]);
+
+        let body = json!({ "edges": [[1, 2]], "nodes": [7] });
+        let (_, job) = server.request("POST", "/api/jobs", Some(&body));
+        let job = server.wait(job["id"].as_u64().unwrap());
+        assert_eq!(job["result"]["size"], 2);
+    }
+
+    #[test]
+    fn test_upload_errors() {
+        let server = Server::start(1);
+        let (status, error) = server.send("POST", "/api/jobs/upload", b"p edge 2 1\ne 1 x\n");
+        assert_eq!(status, 400);
+        assert_eq!(error["line"], 2);
+        assert_eq!(error["column"], 5);
+        assert_eq!(error["kind"], "invalid number `x`");
+
+        let (status, error) = server.send("POST", "/api/jobs/upload", b"p edge 2 1\ne 1 3\n");
+        assert_eq!(status, 400);
+        assert_eq!(error["kind"], "vertex 3 out of range 1..=2");
+
+        let (status, error) = server.send("POST", "/api/jobs/upload?format=xlsx", b"1 2\n");
+        assert_eq!(status, 400);
+        assert_eq!(error["error"], "unknown format `xlsx`");
+
+        // 声明的节点数在分配之前检查
+        let (status, error) = server.send("POST", "/api/jobs/upload", b"p edge 4000000000 0");
+        assert_eq!(status, 400);
+        assert_eq!(error["kind"], "too many vertices: 4000000000");
+        assert_eq!(server.solve_file("", b"p edge 3 0\n")["result"]["size"], 1);
+    }
+
+    #[test]
+    fn test_upload_max_vertices() {
+        let server = Server::start_with(1, &[("CLIQUE_MAX_VERTICES", "10")]);
+        let job = server.solve_file("", b"p edge 10 1\ne 1 10\n");
+        assert_eq!(job["result"]["size"], 2);
+        for body in [&b"p edge 11 0\n"[..], b"% metis\n11 0\n", b"p tw 11 0\n"] {
+            let (status, error) = server.send("POST", "/api/jobs/upload", body);
+            assert_eq!(status, 400);
+            assert_eq!(error["kind"], "too many vertices: 11");
+        }
+    }
+
+    #[test]
+    fn test_solver_options() {
+        let server = Server::start(2);
+        let solve = |body: &Value| {
+            let (status, job) = server.request("POST", "/api/jobs", Some(body));
+            assert_eq!(status, 202, "{job}");
+            server.wait(job["id"].as_u64().unwrap())["result"].clone()
+        };
+        let mut body = edges("data/brock200_2.clq");
+
+        body["algorithm"] = json!("mcs");
+        let result = solve(&body);
+        assert_eq!(result["algorithm"], "mcs");
+        assert_eq!(result["optimal"], true);
+        assert_eq!(result["upper_bound"], 12);
+
+        // 固定种子的遗传算法结果可复现
+        body["algorithm"] = json!("genetic");
+        body["options"] = json!({ "seed": 7, "preprocess": "off" });
+        body["ga_config"] = json!({ "population_size": 20, "max_generations": 50 });
+        let first = solve(&body);
+        let second = solve(&body);
+        assert_eq!(first["algorithm"], "genetic");
+        assert_eq!(first["max_clique"], second["max_clique"]);
+        assert!(first["upper_bound"].as_u64() >= first["size"].as_u64());
+
+        // 时间限制从任务开始运行时计算
+        let mut hard = edges("data/C250.9.clq");
+        hard["algorithm"] = json!("mcs");
+        hard["options"] = json!({ "time_limit": 0.2 });
+        let result = solve(&hard);
+        assert_eq!(result["status"], "timed-out");
+        assert_eq!(result["optimal"], false);
+        assert!(result["upper_bound"].as_u64() > result["size"].as_u64());
+
+        let dimacs = std::fs::read("data/small.clq").unwrap();
+        let job = server.solve_file("?algorithm=bk&preprocess=off", &dimacs);
+        assert_eq!(job["result"]["algorithm"], "bk");
+        assert_eq!(job["result"]["size"], 3);
+    }
+
+    #[test]
+    fn test_solver_option_errors() {
+        let server = Server::start(1);
+        let error = |body: Value| {
+            let (status, error) = server.request("POST", "/api/jobs", Some(&body));
+            assert_eq!(status, 400, "{error}");
+            error["error"].as_str().unwrap().to_string()
+        };
+        let edges = json!([[1, 2]]);
+        assert_eq!(
+            error(json!({ "edges": edges, "algorithm": "simplex" })),
+            "unknown algorithm `simplex`"
+        );
+        assert_eq!(
+            error(json!({ "edges": edges, "options": { "preprocess": "all" } })),
+            "unknown preprocess mode `all`"
+        );
+        assert_eq!(
+            error(json!({ "edges": edges, "options": { "time_limit": -1 } })),
+            "invalid time_limit `-1`"
+        );
+        assert_eq!(
+            error(json!({ "edges": edges, "options": { "time_limit": 1e30 } })),
+            "invalid time_limit `1000000000000000000000000000000`"
+        );
+        let (status, body) = server.send("POST", "/api/jobs/upload?time_limit=1e30", b"1 2\n");
+        assert_eq!(status, 400);
+        assert!(
+            body["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("invalid time_limit")
+        );
+
+        // 在 Duration 范围内但超出 Instant 范围的时间限制不会让工作线程崩溃
+        let body = json!({ "edges": edges, "options": { "time_limit": 1e19 } });
+        let (status, job) = server.request("POST", "/api/jobs", Some(&body));
+        assert_eq!(status, 202);
+        let job = server.wait(job["id"].as_u64().unwrap());
+        assert_eq!(job["result"]["status"], "optimal");
+        assert!(error(json!({ "edges": edges, "options": { "timeout": 1 } })).contains("timeout"));
+
+        let (status, error) = server.send("POST", "/api/jobs/upload?seed=x", b"1 2\n");
+        assert_eq!(status, 400);
+        assert!(
+            error["error"].as_str().unwrap().contains("Query"),
+            "{error}"
+        );
+        let (status, error) = server.send("POST", "/api/jobs/upload?algorithm=ga2", b"1 2\n");
+        assert_eq!(status, 400);
+        assert_eq!(error["error"], "unknown algorithm `ga2`");
+    }
+
+    #[test]
+    fn test_datasets() {
+        let server = Server::start(2);
+        let (status, datasets) = server.request("GET", "/api/datasets", None);
+        assert_eq!(status, 200);
+        let datasets = datasets.as_array().unwrap();
+        assert_eq!(datasets.len(), std::fs::read_dir("data").unwrap().count());
+        let brock = datasets
+            .iter()
+            .find(|d| d["name"] == "brock200_2.clq")
+            .unwrap();
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        assert_eq!(brock["nodes"], 200);
+        assert_eq!(brock["edges"], graph.edge_count());
+        assert_eq!(brock["known_optimum"], 12);
+        let density = brock["density"].as_f64().unwrap();
+        assert!((density - 2.0 * graph.edge_count() as f64 / (200.0 * 199.0)).abs() < 1e-9);
+        let small = datasets.iter().find(|d| d["name"] == "small.clq").unwrap();
+        assert_eq!(small["known_optimum"], Value::Null);
+
+        // 返回的图可以直接提交为任务
+        let (status, small) = server.request("GET", "/api/datasets/small.clq", None);
+        assert_eq!(status, 200);
+        assert_eq!(small["info"]["name"], "small.clq");
+        assert_eq!(
+            small["nodes"].as_array().unwrap().len(),
+            small["info"]["nodes"].as_u64().unwrap() as usize
+        );
+        let (_, job) = server.request("POST", "/api/jobs", Some(&small));
+        assert_eq!(
+            server.wait(job["id"].as_u64().unwrap())["result"]["size"],
+            3
+        );
+
+        let (status, job) = server.request("POST", "/api/datasets/brock200_4.clq/solve", None);
+        assert_eq!(status, 202, "{job}");
+        let job = server.wait(job["id"].as_u64().unwrap());
+        assert_eq!(job["result"]["size"], 17);
+
+        let body = json!({ "algorithm": "mcs", "options": { "preprocess": "off" } });
+        let (_, job) = server.request("POST", "/api/datasets/small.clq/solve", Some(&body));
+        let job = server.wait(job["id"].as_u64().unwrap());
+        assert_eq!(job["result"]["algorithm"], "mcs");
+        assert_eq!(job["result"]["size"], 3);
+
+        let body = json!({ "algorithm": "simplex" });
+        let (status, error) = server.request("POST", "/api/datasets/small.clq/solve", Some(&body));
+        assert_eq!(status, 400);
+        assert_eq!(error["error"], "unknown algorithm `simplex`");
+        for path in [
+            "/api/datasets/missing.clq",
+            "/api/datasets/..%2FCargo.toml",
+            "/api/datasets/missing.clq/solve",
+        ] {
+            let method = if path.ends_with("solve") {
+                "POST"
+            } else {
+                "GET"
+            };
+            assert_eq!(server.request(method, path, None).0, 404, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_datasets_with_broken_file() {
+        let dir = std::env::temp_dir().join(format!("clique-api-{}-datasets", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::copy("data/small.clq", dir.join("small.clq")).unwrap();
+        std::fs::write(dir.join("broken.clq"), "p edge 3 1\ne 1 x\n").unwrap();
+
+        // 解析失败的文件单独报告错误，其它文件照常列出
+        let server = Server::start_with(1, &[("CLIQUE_DATA_DIR", dir.to_str().unwrap())]);
+        let (status, datasets) = server.request("GET", "/api/datasets", None);
+        assert_eq!(status, 200);
+        assert_eq!(
+            datasets[0],
+            json!({
+                "name": "broken.clq",
+                "error": "invalid graph file at line 2, column 5: invalid number `x`",
+            })
+        );
+        assert_eq!(datasets[1]["name"], "small.clq");
+        assert_eq!(datasets[1]["nodes"], 5);
+        assert_eq!(
+            server.request("GET", "/api/datasets/broken.clq", None).0,
+            500
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_max_clique_compat() {
+        let server = Server::start(1);
+        // 三角形 10-20-30 和悬挂边 30-99
+        let body = json!({ "edges": [[10, 20], [20, 30], [30, 10], [30, 99]] });
+        let (status, response) = server.request("POST", "/api/find-max-clique", Some(&body));
+        assert_eq!(status, 200);
+        let mut clique: Vec<u64> = response["max_clique"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|u| u.as_u64().unwrap())
+            .collect();
+        clique.sort();
+        assert_eq!(clique, vec![10, 20, 30]);
+
+        assert_eq!(server.request("GET", "/api/jobs/12345", None).0, 404);
+        let (status, error) = server.request("POST", "/api/jobs", Some(&json!({ "edges": "x" })));
+        assert_eq!(status, 400);
+        assert!(error["error"].as_str().unwrap().contains("invalid type"));
+    }
+}
@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::process::{Command, Output, Stdio};
+
+    fn clique(args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_clique"))
+            .args(args)
+            .output()
+            .unwrap()
+    }
+
+    fn clique_with_stdin(args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_clique"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    fn stdout(output: &Output) -> String {
+        String::from_utf8(output.stdout.clone()).unwrap()
+    }
+
+    fn stderr(output: &Output) -> String {
+        String::from_utf8(output.stderr.clone()).unwrap()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("clique-cli-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[rstest]
+    #[case("bk")]
+    #[case("mcs")]
+    #[case("sparse")]
+    #[case("portfolio")]
+    fn test_solve_json(#[case] algorithm: &str) {
+        let output = clique(&[
+            "solve",
+            "data/brock200_2.clq",
+            "--algorithm",
+            algorithm,
+            "-f",
+            "json",
+        ]);
+        assert!(output.status.success(), "{}", stderr(&output));
+        let report: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+        assert_eq!(report["size"], 12);
+        assert_eq!(report["clique"].as_array().unwrap().len(), 12);
+        assert_eq!(report["status"], "optimal");
+        assert_eq!(report["upper_bound"], 12);
+        assert_eq!(report["algorithm"], algorithm);
+    }
+
+    #[test]
+    fn test_solve_then_verify() {
+        for format in ["text", "json"] {
+            let solved = clique(&["solve", "data/brock200_2.clq", "--seed=7", "-f", format]);
+            assert!(solved.status.success());
+            let verified = clique_with_stdin(
+                &["verify", "--maximal", "data/brock200_2.clq", "-"],
+                &stdout(&solved),
+            );
+            assert!(verified.status.success(), "{}", stderr(&verified));
+            assert!(stdout(&verified).starts_with("ok: clique of size 12"));
+        }
+    }
+
+    #[test]
+    fn test_verify_failures() {
+        // small.clq: 1-2, 2-3, 1-4, 2-4, 2-5, 4-5
+        let output = clique_with_stdin(&["verify", "data/small.clq", "-"], "1 3\n");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("`1` and `3` are not adjacent"));
+
+        let output = clique_with_stdin(&["verify", "data/small.clq", "-"], "# comment\n1 2\n");
+        assert!(output.status.success());
+        assert!(stdout(&output).contains("not maximal, node `4` can be added"));
+        let output = clique_with_stdin(&["verify", "--maximal", "data/small.clq", "-"], "1 2");
+        assert_eq!(output.status.code(), Some(1));
+
+        let output = clique_with_stdin(&["verify", "data/small.clq", "-"], "1 9");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("node `9` is not in the graph"));
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let dir = temp_dir("exit");
+        let bad = dir.join("bad.clq");
+        std::fs::write(&bad, "p edge 2 1\ne 1 x\n").unwrap();
+        let output = clique(&["solve", bad.to_str().unwrap()]);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr(&output).contains("line 2, column 5"));
+
+        let output = clique(&["solve", "data/does-not-exist.clq"]);
+        assert_eq!(output.status.code(), Some(1));
+
+        // 用法错误
+        for args in [
+            &["solve", "data/small.clq", "--bogus", "1"][..],
+            &["solve", "data/small.clq", "-a", "quantum"],
+            &["solve", "data/small.clq", "-t", "-1"],
+            &["solve", "data/small.clq", "-t", "1e30"],
+            &["solve", "data/small.clq", "-t", "inf"],
+            &["solve"],
+            &["stats", "data/small.clq", "-f", "dot"],
+            &["frobnicate"],
+            &[],
+        ] {
+            assert_eq!(clique(args).status.code(), Some(2), "{args:?}");
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_stdin_and_time_limit() {
+        let graph = std::fs::read_to_string("data/small.clq").unwrap();
+        let output = clique_with_stdin(&["solve", "-", "-t", "10"], &graph);
+        assert!(output.status.success());
+        assert_eq!(stdout(&output).split_whitespace().count(), 3);
+        // 超出 Instant 范围的时间限制等同于不限时
+        let output = clique(&["solve", "data/small.clq", "-t", "1e19"]);
+        assert!(output.status.success(), "{}", stderr(&output));
+
+        let output = clique(&[
+            "solve",
+            "data/C250.9.clq",
+            "-a",
+            "mcs",
+            "-t",
+            "0.2",
+            "-f",
+            "json",
+        ]);
+        assert!(output.status.success());
+        let report: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+        assert_eq!(report["status"], "timed-out");
+    }
+
+    #[rstest]
+    #[case("graph.graph", None)]
+    #[case("graph.txt", None)]
+    #[case("graph.graphml", None)]
+    #[case("graph.clq.b", None)]
+    #[case("graph.out", Some("dimacs"))]
+    fn test_convert_round_trip(#[case] name: &str, #[case] format: Option<&str>) {
+        let dir = temp_dir(name);
+        let out = dir.join(name);
+        let out = out.to_str().unwrap();
+        let mut args = vec!["convert", "data/brock200_2.clq", out];
+        if let Some(format) = format {
+            args.extend(["--output-format", format]);
+        }
+        assert!(clique(&args).status.success());
+
+        let stats = |path: &str, extra: &[&str]| {
+            let mut args = vec!["stats", path, "-f", "json"];
+            args.extend(extra);
+            let output = clique(&args);
+            assert!(output.status.success(), "{}", stderr(&output));
+            serde_json::from_str::<serde_json::Value>(&stdout(&output)).unwrap()
+        };
+        // 没有可识别的扩展名时显式指定输入格式
+        let extra: &[&str] = if format.is_some() {
+            &["-i", "dimacs"]
+        } else {
+            &[]
+        };
+        let converted = stats(out, extra);
+        let original = stats("data/brock200_2.clq", &[]);
+        assert_eq!(converted["nodes"], 200);
+        assert_eq!(converted, original);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_convert_keeps_comments() {
+        let dir = temp_dir("comments");
+        let input = std::fs::read_to_string("data/brock200_2.clq").unwrap();
+        let comments: Vec<&str> = input.lines().filter(|l| l.starts_with('c')).collect();
+        assert!(!comments.is_empty());
+
+        let out = dir.join("out.clq");
+        let out = out.to_str().unwrap();
+        assert!(
+            clique(&["convert", "data/brock200_2.clq", out])
+                .status
+                .success()
+        );
+        let written = std::fs::read_to_string(out).unwrap();
+        let written: Vec<&str> = written.lines().filter(|l| l.starts_with('c')).collect();
+        assert_eq!(written, comments);
+
+        // 从标准输入读取时按内容识别 DIMACS
+        let output = clique_with_stdin(&["convert", "-", "-", "-o", "dimacs"], &input);
+        assert!(output.status.success(), "{}", stderr(&output));
+        let written = stdout(&output);
+        let written: Vec<&str> = written.lines().filter(|l| l.starts_with('c')).collect();
+        assert_eq!(written, comments);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_bench() {
+        let dir = temp_dir("bench");
+        std::fs::copy("data/small.clq", dir.join("small.clq")).unwrap();
+        std::fs::copy("data/brock200_4.clq", dir.join("brock200_4.clq")).unwrap();
+        std::fs::write(dir.join("notes.md"), "not a graph").unwrap();
+        let dir_str = dir.to_str().unwrap();
+
+        let output = clique(&["bench", dir_str, "-a", "mcs", "-f", "json"]);
+        assert!(output.status.success(), "{}", stderr(&output));
+        let rows: Vec<serde_json::Value> = stdout(&output)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["file"], "brock200_4.clq");
+        assert_eq!(rows[0]["size"], 17);
+        assert_eq!(rows[1]["file"], "small.clq");
+        assert_eq!(rows[1]["size"], 3);
+
+        // 无法解析的文件报告后继续，最后以非零状态退出
+        std::fs::write(dir.join("broken.clq"), "p edge 2\n").unwrap();
+        let output = clique(&["bench", dir_str]);
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(stdout(&output).lines().count(), 3);
+        assert!(stderr(&output).contains("broken.clq"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
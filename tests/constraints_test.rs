@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod tests {
+    use clique::constraints::{
+        ConstraintError, Constraints, find_max_clique_with, find_max_clique_with_by,
+    };
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::{Algorithm, find_max_cliques_by};
+    use clique::verify::{VerifyError, check_clique};
+    use petgraph::graph::{NodeIndex, UnGraph};
+    use rand::prelude::*;
+    use rstest::rstest;
+
+    fn nodes(indices: &[usize]) -> Vec<NodeIndex> {
+        indices.iter().map(|&i| NodeIndex::new(i)).collect()
+    }
+
+    fn random_graph(rng: &mut StdRng, n: usize, p: f64) -> UnGraph<(), ()> {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+        for i in 0..n {
+            for j in i + 1..n {
+                if rng.random_bool(p) {
+                    graph.add_edge(nodes[i], nodes[j], ());
+                }
+            }
+        }
+        graph
+    }
+
+    // 枚举所有子集求满足约束的最大团规模
+    fn brute_force(graph: &UnGraph<(), ()>, constraints: &Constraints) -> usize {
+        let n = graph.node_count();
+        let mask = |set: &[NodeIndex]| set.iter().fold(0u32, |m, u| m | 1 << u.index());
+        let (include, exclude) = (
+            mask(&constraints.must_include),
+            mask(&constraints.must_exclude),
+        );
+        (0u32..1 << n)
+            .filter(|&s| s & include == include && s & exclude == 0)
+            .filter(|&s| {
+                let members: Vec<_> = (0..n).filter(|&i| s >> i & 1 == 1).collect();
+                check_clique(graph, &nodes(&members)).is_ok()
+            })
+            .map(|s| s.count_ones() as usize)
+            .max()
+            .unwrap()
+    }
+
+    fn assert_satisfies(graph: &UnGraph<(), ()>, constraints: &Constraints, clique: &[NodeIndex]) {
+        assert_eq!(check_clique(graph, clique), Ok(()));
+        assert!(constraints.must_include.iter().all(|u| clique.contains(u)));
+        assert!(!constraints.must_exclude.iter().any(|u| clique.contains(u)));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(31);
+        for _ in 0..40 {
+            let n = rng.random_range(2..13);
+            let p = rng.random_range(0.3..0.9);
+            let graph = random_graph(&mut rng, n, p);
+            // 必选节点取一条边（或一个节点），再随机排除几个其它节点
+            let mut include = vec![rng.random_range(0..n)];
+            let neighbors: Vec<_> = graph.neighbors(NodeIndex::new(include[0])).collect();
+            if let Some(&v) = neighbors.choose(&mut rng) {
+                include.push(v.index());
+            }
+            let exclude: Vec<usize> = (0..n)
+                .filter(|i| !include.contains(i) && rng.random_bool(0.2))
+                .collect();
+            let constraints = Constraints::new()
+                .include(nodes(&include))
+                .exclude(nodes(&exclude));
+            let expected = brute_force(&graph, &constraints);
+
+            for algorithm in [Algorithm::BronKerbosch, Algorithm::Mcs, Algorithm::Auto] {
+                let clique = find_max_clique_with_by(&graph, &constraints, algorithm).unwrap();
+                assert_eq!(clique.len(), expected, "{algorithm:?}");
+                assert_satisfies(&graph, &constraints, &clique);
+            }
+            let clique = find_max_clique_with_by(&graph, &constraints, Algorithm::Genetic).unwrap();
+            assert!(clique.len() <= expected);
+            assert_satisfies(&graph, &constraints, &clique);
+        }
+    }
+
+    #[rstest]
+    #[case(Algorithm::BronKerbosch)]
+    #[case(Algorithm::Genetic)]
+    #[case(Algorithm::Mcs)]
+    fn test_dataset_with_seed_vertex(#[case] algorithm: Algorithm) {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let best = find_max_cliques_by(&graph, Algorithm::Mcs);
+        let seed = best[0];
+
+        // 最大团中的节点作为种子时，带约束的最优解规模不变
+        let constraints = Constraints::new().include([seed]);
+        let clique = find_max_clique_with_by(&graph, &constraints, algorithm).unwrap();
+        assert_satisfies(&graph, &constraints, &clique);
+        assert!(clique.len() <= best.len());
+        if algorithm != Algorithm::Genetic {
+            assert_eq!(clique.len(), best.len());
+        }
+
+        // 排除最大团中的一个节点
+        let constraints = Constraints::new().exclude([seed]);
+        let clique = find_max_clique_with_by(&graph, &constraints, algorithm).unwrap();
+        assert_satisfies(&graph, &constraints, &clique);
+    }
+
+    #[rstest]
+    #[case(Algorithm::BronKerbosch)]
+    #[case(Algorithm::Genetic)]
+    #[case(Algorithm::Mcs)]
+    fn test_initial_solution(#[case] algorithm: Algorithm) {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let best = find_max_cliques_by(&graph, Algorithm::Mcs);
+        let constraints = Constraints::new()
+            .include([best[0]])
+            .initial_solution(best.clone());
+        let clique = find_max_clique_with_by(&graph, &constraints, algorithm).unwrap();
+        assert_eq!(clique.len(), best.len());
+        assert_satisfies(&graph, &constraints, &clique);
+    }
+
+    #[rstest]
+    #[case(Algorithm::Auto)]
+    #[case(Algorithm::Mcs)]
+    #[case(Algorithm::ParallelMcs)]
+    #[case(Algorithm::Sparse)]
+    #[case(Algorithm::Portfolio)]
+    fn test_initial_solution_is_bound(#[case] algorithm: Algorithm) {
+        // 8 个节点的完全图去掉 (0,1) (2,3) (4,5) (6,7)：每对各选一个都是最大团
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let all: Vec<_> = (0..8).map(|_| graph.add_node(())).collect();
+        for i in 0..8 {
+            for j in i + 1..8 {
+                if i / 2 != j / 2 {
+                    graph.add_edge(all[i], all[j], ());
+                }
+            }
+        }
+        let constraints = Constraints::new().include(nodes(&[0]));
+        let found = find_max_clique_with_by(&graph, &constraints, algorithm).unwrap();
+        assert_eq!(found.len(), 4);
+
+        // 换成与搜索结果不同的另一个最大团作为初始解：搜索只接受更大的团，结果就是初始解
+        let mut initial: Vec<_> = (1..4)
+            .map(|pair| {
+                let u = NodeIndex::new(2 * pair);
+                if found.contains(&u) {
+                    NodeIndex::new(2 * pair + 1)
+                } else {
+                    u
+                }
+            })
+            .collect();
+        initial.push(NodeIndex::new(0));
+        initial.sort();
+        let mut clique = find_max_clique_with_by(
+            &graph,
+            &constraints.clone().initial_solution(initial.clone()),
+            algorithm,
+        )
+        .unwrap();
+        clique.sort();
+        assert_eq!(clique, initial);
+    }
+
+    #[test]
+    fn test_required_set_is_whole_answer() {
+        // 三角形 0-1-2，3 只与 0 相邻
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (0, 3)]);
+        let constraints = Constraints::new().include(nodes(&[0, 3]));
+        for algorithm in [Algorithm::BronKerbosch, Algorithm::Genetic, Algorithm::Auto] {
+            let mut clique = find_max_clique_with_by(&graph, &constraints, algorithm).unwrap();
+            clique.sort();
+            assert_eq!(clique, nodes(&[0, 3]));
+        }
+        let constraints = Constraints::new().exclude(nodes(&[0, 1, 2, 3]));
+        assert_eq!(find_max_clique_with(&graph, &constraints), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (0, 3)]);
+        let check = |constraints: Constraints| find_max_clique_with(&graph, &constraints);
+
+        assert_eq!(
+            check(Constraints::new().include(nodes(&[1, 3]))),
+            Err(ConstraintError::Infeasible(VerifyError::NotAdjacent(
+                NodeIndex::new(1),
+                NodeIndex::new(3)
+            )))
+        );
+        assert_eq!(
+            check(Constraints::new().include(nodes(&[7]))),
+            Err(ConstraintError::Infeasible(VerifyError::NodeOutOfRange(
+                NodeIndex::new(7)
+            )))
+        );
+        assert_eq!(
+            check(Constraints::new().exclude(nodes(&[9]))),
+            Err(ConstraintError::NodeOutOfRange(NodeIndex::new(9)))
+        );
+        assert_eq!(
+            check(Constraints::new().include(nodes(&[0])).exclude(nodes(&[0]))),
+            Err(ConstraintError::Conflict(NodeIndex::new(0)))
+        );
+        assert_eq!(
+            check(Constraints::new().initial_solution(nodes(&[1, 3]))),
+            Err(ConstraintError::InvalidInitialSolution(
+                VerifyError::NotAdjacent(NodeIndex::new(1), NodeIndex::new(3))
+            ))
+        );
+        assert_eq!(
+            check(
+                Constraints::new()
+                    .include(nodes(&[3]))
+                    .initial_solution(nodes(&[0, 1]))
+            ),
+            Err(ConstraintError::InitialSolutionViolates(NodeIndex::new(3)))
+        );
+        assert_eq!(
+            check(
+                Constraints::new()
+                    .exclude(nodes(&[2]))
+                    .initial_solution(nodes(&[0, 2]))
+            ),
+            Err(ConstraintError::InitialSolutionViolates(NodeIndex::new(2)))
+        );
+    }
+}
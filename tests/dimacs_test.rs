@@ -1,7 +1,27 @@
 #[cfg(test)]
 mod tests {
-    use clique::graph_reader::read_dimacs;
+    use clique::graph_reader::{
+        DimacsOptions, GraphErr, ParseErrorKind, read_dimacs, read_dimacs_binary,
+        read_dimacs_binary_from, read_dimacs_from, read_dimacs_from_with,
+    };
+    use clique::graph_writer::write_dimacs_binary;
     use clique::max_clique::find_max_cliques;
+    use flate2::write::GzEncoder;
+    use petgraph::graph::UnGraph;
+    use rstest::rstest;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use xz2::write::XzEncoder;
+
+    fn write_temp(name: &str, content: impl AsRef<[u8]>) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("clique_dimacs_{name}.clq"));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn read_str(content: &str, options: &DimacsOptions) -> Result<UnGraph<(), ()>, GraphErr> {
+        read_dimacs_from_with(content.as_bytes(), options)
+    }
 
     #[test]
     fn test_read() {
@@ -31,4 +51,223 @@ mod tests {
         let clique = find_max_cliques(&graph);
         assert_eq!(clique.len(), 12);
     }
+
+    #[rstest]
+    #[case("e 1 2\np edge 2 1\n", 1, 1, ParseErrorKind::MissingProblemLine)]
+    #[case("p edge 2 1\ne 0 2\n", 2, 3, ParseErrorKind::VertexOutOfRange { vertex: 0, node_count: 2 })]
+    #[case("p edge 2 1\ne 1  3\n", 2, 6, ParseErrorKind::VertexOutOfRange { vertex: 3, node_count: 2 })]
+    #[case("p edge 2 1\ne 1 x\n", 2, 5, ParseErrorKind::InvalidNumber("x".into()))]
+    #[case("p edge 2 1\ne 1\n", 2, 4, ParseErrorKind::MissingToken("vertex"))]
+    #[case("p edge 2 1\ne 1 2 3\n", 2, 7, ParseErrorKind::UnexpectedToken("3".into()))]
+    #[case("p graph 2 1\n", 1, 3, ParseErrorKind::UnsupportedFormat("graph".into()))]
+    #[case("p edge 2 1\np edge 2 1\n", 2, 1, ParseErrorKind::DuplicateProblemLine)]
+    #[case("c ok\nx 1 2\n", 2, 1, ParseErrorKind::UnexpectedToken("x".into()))]
+    #[case("p edge 2 2\ne 1 2\ne 2 2\n", 3, 1, ParseErrorKind::SelfLoop(2))]
+    #[case(
+        "p edge 2 2\ne 1 2\ne 2 1\n",
+        3,
+        1,
+        ParseErrorKind::DuplicateEdge(2, 1)
+    )]
+    fn test_parse_error(
+        #[case] content: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] kind: ParseErrorKind,
+    ) {
+        match read_str(content, &DimacsOptions::new()) {
+            Err(GraphErr::ParseError {
+                line: l,
+                column: c,
+                kind: k,
+            }) => assert_eq!((l, c, k), (line, column, kind)),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_max_vertices() {
+        // 低于 u32 上限，但远超默认上限
+        match read_str("p edge 4000000000 0\n", &DimacsOptions::new()) {
+            Err(GraphErr::ParseError { line, column, kind }) => assert_eq!(
+                (line, column, kind),
+                (1, 8, ParseErrorKind::TooManyVertices(4_000_000_000))
+            ),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        let options = DimacsOptions::new().max_vertices(3);
+        assert_eq!(read_str("p edge 3 0\n", &options).unwrap().node_count(), 3);
+        assert!(read_str("p edge 4 0\n", &options).is_err());
+        // 二进制格式的头部同样受限
+        let binary = b"20\np edge 4000000000 0\n";
+        let path = write_temp("huge_b", binary);
+        let result = read_dimacs_binary(path.to_str().unwrap());
+        std::fs::remove_file(path).unwrap();
+        assert!(matches!(
+            result,
+            Err(GraphErr::ParseError {
+                kind: ParseErrorKind::TooManyVertices(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bom() {
+        let graph = read_str(
+            "\u{feff}c comment\np edge 2 1\ne 1 2\n",
+            &DimacsOptions::new(),
+        )
+        .unwrap();
+        assert_eq!((graph.node_count(), graph.edge_count()), (2, 1));
+    }
+
+    #[test]
+    fn test_col_and_comments() {
+        let content = "c comment\n\np col 3 2\ne 1 2\n  e\t2 3\n";
+        let graph = read_str(content, &DimacsOptions::new()).unwrap();
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 2));
+    }
+
+    #[test]
+    fn test_dedup() {
+        let content = "p edge 3 4\ne 1 2\ne 2 1\ne 3 3\ne 2 3\n";
+        let graph = read_str(content, &DimacsOptions::new().dedup(true)).unwrap();
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_edge_count() {
+        // 默认不校验声明的边数
+        let graph = read_str("p edge 2 5\ne 1 2\n", &DimacsOptions::new()).unwrap();
+        assert_eq!(graph.edge_count(), 1);
+
+        // 严格校验时按有向边计数的声明也可以接受
+        let strict = DimacsOptions::new().skip_edge_count_check(false);
+        let graph = read_str("p edge 2 2\ne 1 2\n", &strict).unwrap();
+        assert_eq!(graph.edge_count(), 1);
+        match read_str("p edge 3 3\ne 1 2\ne 2 3\n", &strict) {
+            Err(GraphErr::ParseError { line, column, kind }) => assert_eq!(
+                (line, column, kind),
+                (
+                    1,
+                    1,
+                    ParseErrorKind::EdgeCountMismatch {
+                        declared: 3,
+                        actual: 2
+                    }
+                )
+            ),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        let small = std::fs::read_to_string("data/small.clq").unwrap();
+        assert!(read_str(&small, &strict).is_err());
+    }
+
+    #[test]
+    fn test_all_datasets() {
+        for entry in std::fs::read_dir("data").unwrap() {
+            let path = entry.unwrap().path();
+            assert!(read_dimacs(path.to_str().unwrap()).is_ok(), "{path:?}");
+        }
+    }
+
+    fn sorted_edges(graph: &UnGraph<(), ()>) -> Vec<(usize, usize)> {
+        let mut edges: Vec<_> = graph
+            .edge_indices()
+            .map(|e| {
+                let (a, b) = graph.edge_endpoints(e).unwrap();
+                (a.index().min(b.index()), a.index().max(b.index()))
+            })
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    #[rstest]
+    #[case("small")]
+    #[case("MANN_a27")]
+    #[case("keller4")]
+    fn test_binary_round_trip(#[case] name: &str) {
+        let graph = read_dimacs(&format!("data/{name}.clq")).unwrap();
+        let mut bytes = Vec::new();
+        write_dimacs_binary(&graph, &mut bytes).unwrap();
+
+        let path = write_temp(&format!("{name}_b"), &bytes);
+        let path = path.to_str().unwrap();
+        let binary = read_dimacs_binary(path).unwrap();
+        // read_dimacs 根据文件开头自动识别二进制格式
+        let detected = read_dimacs(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(binary.node_count(), graph.node_count());
+        assert_eq!(sorted_edges(&binary), sorted_edges(&graph));
+        assert_eq!(sorted_edges(&detected), sorted_edges(&graph));
+    }
+
+    #[test]
+    fn test_binary_layout() {
+        // 边 1-2 与 1-3，第 i 行占 i / 8 + 1 字节，最高位对应第 0 列
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (2, 2)]);
+        let mut bytes = Vec::new();
+        write_dimacs_binary(&graph, &mut bytes).unwrap();
+        assert_eq!(bytes, b"11\np edge 3 2\n\x00\x80\x80");
+    }
+
+    #[test]
+    fn test_binary_truncated() {
+        let path = write_temp("truncated_b", b"11\np edge 9 1\n\x00\x80");
+        let result = read_dimacs_binary(path.to_str().unwrap());
+        std::fs::remove_file(path).unwrap();
+        assert!(matches!(
+            result,
+            Err(GraphErr::ParseError {
+                kind: ParseErrorKind::TruncatedBitmap(2),
+                ..
+            })
+        ));
+    }
+
+    fn compress(format: &str, bytes: &[u8]) -> Vec<u8> {
+        match format {
+            "gz" => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(bytes).unwrap();
+                encoder.finish().unwrap()
+            }
+            "xz" => {
+                let mut encoder = XzEncoder::new(Vec::new(), 6);
+                encoder.write_all(bytes).unwrap();
+                encoder.finish().unwrap()
+            }
+            "zst" => zstd::encode_all(bytes, 0).unwrap(),
+            _ => bytes.to_vec(),
+        }
+    }
+
+    #[rstest]
+    #[case("plain")]
+    #[case("gz")]
+    #[case("xz")]
+    #[case("zst")]
+    fn test_read_from_compressed(#[case] format: &str) {
+        let graph = read_dimacs("data/keller4.clq").unwrap();
+
+        let ascii = std::fs::read("data/keller4.clq").unwrap();
+        let compressed = compress(format, &ascii);
+        let from_ascii = read_dimacs_from(compressed.as_slice()).unwrap();
+        assert_eq!(sorted_edges(&from_ascii), sorted_edges(&graph));
+
+        let mut binary = Vec::new();
+        write_dimacs_binary(&graph, &mut binary).unwrap();
+        let compressed = compress(format, &binary);
+        let from_binary = read_dimacs_binary_from(compressed.as_slice()).unwrap();
+        assert_eq!(sorted_edges(&from_binary), sorted_edges(&graph));
+
+        // 按路径读取压缩文件
+        let path = write_temp(&format!("keller4_{format}"), &compressed);
+        let from_file = read_dimacs(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(sorted_edges(&from_file), sorted_edges(&graph));
+    }
 }
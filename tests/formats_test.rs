@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::{
+        Format, GraphErr, LabeledGraph, ParseErrorKind, ReadOptions, read_dimacs, read_graph,
+        read_graph_from, read_graph_from_with,
+    };
+    use clique::graph_writer::{write_edge_list, write_graphml, write_metis};
+    use clique::max_clique::find_max_cliques;
+    use rstest::rstest;
+
+    // 排好序的标签边，方便比较
+    fn labeled_edges(labeled: &LabeledGraph) -> Vec<(String, String)> {
+        let mut edges: Vec<_> = labeled
+            .graph
+            .edge_indices()
+            .map(|e| {
+                let (a, b) = labeled.graph.edge_endpoints(e).unwrap();
+                let (a, b) = (labeled.label(a).to_string(), labeled.label(b).to_string());
+                if a < b { (a, b) } else { (b, a) }
+            })
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    fn pairs(edges: &[(&str, &str)]) -> Vec<(String, String)> {
+        edges
+            .iter()
+            .map(|&(a, b)| (a.to_string(), b.to_string()))
+            .collect()
+    }
+
+    const SNAP: &str = "# Directed graph\n# FromNodeId\tToNodeId\n100\t7\n7\t100\n7 42\n42 100\n42 9001\n9001 9001\n";
+    const METIS: &str = "% triangle with tail\n4 4\n2 3\n1 3\n1 2 4\n3\n";
+    const METIS_WEIGHTED: &str = "4 4 011 1\n5 2 1 3 1\n5 1 1 3 1\n5 1 1 2 1 4 1\n5 3 1\n";
+    const MTX: &str = "%%MatrixMarket matrix coordinate pattern symmetric\n% comment\n4 4 5\n2 1\n3 1\n3 2\n4 3\n4 4\n";
+    const PACE: &str = "c triangle with tail\np tw 4 4\n1 2\n2 3\n1 3\n3 4\n";
+    const GRAPHML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="name" attr.type="string"/>
+  <graph id="G" edgedefault="undirected">
+    <node id="a"><data key="d0">Alice</data></node>
+    <node id="b"/>
+    <node id="c"/>
+    <node id="d"/>
+    <edge source="a" target="b"/>
+    <edge source="b" target="c"/>
+    <edge source="c" target="a"/>
+    <edge source="c" target="d"/>
+  </graph>
+</graphml>
+"#;
+
+    #[rstest]
+    #[case(Format::Metis, METIS)]
+    #[case(Format::Metis, METIS_WEIGHTED)]
+    #[case(Format::MatrixMarket, MTX)]
+    #[case(Format::Pace, PACE)]
+    fn test_numbered_formats(#[case] format: Format, #[case] content: &str) {
+        let expected = pairs(&[("1", "2"), ("1", "3"), ("2", "3"), ("3", "4")]);
+        let labeled = read_graph_from(content.as_bytes(), Some(format)).unwrap();
+        assert_eq!(labeled.graph.node_count(), 4);
+        assert_eq!(labeled_edges(&labeled), expected);
+    }
+
+    #[test]
+    fn test_snap_labels() {
+        let labeled = read_graph_from(SNAP.as_bytes(), Some(Format::Snap)).unwrap();
+        assert_eq!(labeled.labels, vec!["100", "7", "42", "9001"]);
+        assert_eq!(
+            labeled_edges(&labeled),
+            pairs(&[("100", "42"), ("100", "7"), ("42", "7"), ("42", "9001")])
+        );
+
+        // 结果可以用原始 ID 报告
+        let clique = find_max_cliques(&labeled.graph);
+        let mut labels = labeled.labels_of(&clique);
+        labels.sort();
+        assert_eq!(labels, vec!["100", "42", "7"]);
+    }
+
+    #[test]
+    fn test_graphml_labels() {
+        let labeled = read_graph_from(GRAPHML.as_bytes(), Some(Format::GraphMl)).unwrap();
+        assert_eq!(labeled.labels, vec!["a", "b", "c", "d"]);
+        assert_eq!(
+            labeled_edges(&labeled),
+            pairs(&[("a", "b"), ("a", "c"), ("b", "c"), ("c", "d")])
+        );
+
+        // 边写在节点之前
+        let content = "<graphml><graph>\n  <edge source=\"b\" target=\"a\"/>\n  \
+                       <node id=\"a\"/><node id=\"b\"/></graph></graphml>";
+        let labeled = read_graph_from(content.as_bytes(), Some(Format::GraphMl)).unwrap();
+        assert_eq!(labeled.labels, vec!["a", "b"]);
+        assert_eq!(labeled_edges(&labeled), pairs(&[("a", "b")]));
+    }
+
+    #[rstest]
+    #[case(SNAP, Format::Snap)]
+    #[case(METIS, Format::Metis)]
+    #[case(MTX, Format::MatrixMarket)]
+    #[case(PACE, Format::Pace)]
+    #[case(GRAPHML, Format::GraphMl)]
+    #[case("c comment\np edge 2 1\ne 1 2\n", Format::Dimacs)]
+    #[case("11\np edge 2 1\n", Format::Dimacs)]
+    // KONECT 等带 `%` 注释的边列表：行数超出头部声明的节点数，或首行不是 METIS 头部
+    #[case("% sym unweighted\n% 4 4 4\n1 2\n2 3\n3 1\n3 4\n", Format::Snap)]
+    #[case("% weighted\n1 2 0.5\n2 3 0.25\n", Format::Snap)]
+    #[case("% metis\n\n3 2 1\n2 1\n1 1 3 1\n2 1\n\n", Format::Metis)]
+    fn test_detect(#[case] content: &str, #[case] format: Format) {
+        assert_eq!(Format::detect(content.as_bytes()), format);
+    }
+
+    #[rstest]
+    #[case("data/keller4.clq", Some(Format::Dimacs))]
+    #[case("web-Google.txt.gz", Some(Format::Snap))]
+    #[case("road.GRAPH", Some(Format::Metis))]
+    #[case("bcsstk01.mtx.zst", Some(Format::MatrixMarket))]
+    #[case("social.graphml", Some(Format::GraphMl))]
+    #[case("instance.gr.xz", Some(Format::Pace))]
+    #[case("no_extension", None)]
+    fn test_from_path(#[case] path: &str, #[case] format: Option<Format>) {
+        assert_eq!(Format::from_path(path), format);
+    }
+
+    #[test]
+    fn test_read_detected() {
+        let konect = "% sym unweighted\n% 4 4 4\n1 2\n2 3\n3 1\n3 4\n";
+        for content in [SNAP, METIS, MTX, PACE, GRAPHML, konect] {
+            let labeled = read_graph_from(content.as_bytes(), None).unwrap();
+            assert_eq!(labeled.graph.edge_count(), 4);
+        }
+    }
+
+    // 开头的 UTF-8 BOM 在判断格式和解析时都会跳过
+    #[rstest]
+    #[case(SNAP, Format::Snap)]
+    #[case(METIS, Format::Metis)]
+    #[case(MTX, Format::MatrixMarket)]
+    #[case(PACE, Format::Pace)]
+    #[case(GRAPHML, Format::GraphMl)]
+    #[case("p edge 3 2\ne 1 2\ne 2 3\n", Format::Dimacs)]
+    fn test_bom(#[case] content: &str, #[case] format: Format) {
+        let content = format!("\u{feff}{content}");
+        assert_eq!(Format::detect(content.as_bytes()), format);
+        let detected = read_graph_from(content.as_bytes(), None).unwrap();
+        let explicit = read_graph_from(content.as_bytes(), Some(format)).unwrap();
+        assert_eq!(labeled_edges(&detected), labeled_edges(&explicit));
+        assert!(detected.graph.edge_count() > 0);
+        assert!(
+            detected
+                .labels
+                .iter()
+                .all(|label| !label.contains('\u{feff}'))
+        );
+    }
+
+    #[test]
+    fn test_matrix_market_values() {
+        // 实数与整数矩阵只取非零元的位置
+        let content = "%%MatrixMarket matrix coordinate real general\n3 3 4\n1 2 0.5\n2 1 0.5\n2 3 -1e3\n3 3 2\n";
+        let labeled = read_graph_from(content.as_bytes(), None).unwrap();
+        assert_eq!(labeled_edges(&labeled), pairs(&[("1", "2"), ("2", "3")]));
+        let content = "%%MatrixMarket matrix coordinate integer symmetric\n2 2 1\n2 1 7\n";
+        let labeled = read_graph_from(content.as_bytes(), None).unwrap();
+        assert_eq!(labeled_edges(&labeled), pairs(&[("1", "2")]));
+    }
+
+    #[test]
+    fn test_read_graph_dimacs() {
+        let labeled = read_graph("data/brock200_2.clq").unwrap();
+        assert_eq!(labeled.graph.edge_count(), 9876);
+        assert_eq!(labeled.label(0.into()), "1");
+    }
+
+    // 用 graph_writer 写出后再读回，最大团规模不变
+    #[rstest]
+    #[case(Format::Metis)]
+    #[case(Format::GraphMl)]
+    #[case(Format::Snap)]
+    fn test_writer_round_trip(#[case] format: Format) {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let mut out = Vec::new();
+        match format {
+            Format::Metis => write_metis(&graph, &mut out).unwrap(),
+            Format::GraphMl => write_graphml(&graph, &mut out).unwrap(),
+            _ => write_edge_list(&graph, &mut out).unwrap(),
+        }
+        let labeled = read_graph_from(out.as_slice(), Some(format)).unwrap();
+        assert_eq!(labeled.graph.edge_count(), graph.edge_count());
+        assert_eq!(find_max_cliques(&labeled.graph).len(), 12);
+    }
+
+    #[rstest]
+    #[case(Format::Metis, "3 1\n2\n1 4\n", 3, 3, ParseErrorKind::VertexOutOfRange { vertex: 4, node_count: 3 })]
+    #[case(Format::Metis, "3 5\n2\n1\n\n", 1, 1, ParseErrorKind::EdgeCountMismatch { declared: 5, actual: 1 })]
+    #[case(Format::Metis, "3 1\n2\n1\n", 1, 1, ParseErrorKind::NodeCountMismatch { declared: 3, actual: 2 })]
+    #[case(Format::MatrixMarket, "%%MatrixMarket matrix array real general\n", 1, 1, ParseErrorKind::InvalidHeader("%%MatrixMarket matrix array real general".into()))]
+    #[case(Format::MatrixMarket, "%%MatrixMarket matrix coordinate complex general\n2 2 1\n2 1 1.0 0.5\n", 1, 1, ParseErrorKind::InvalidHeader("%%MatrixMarket matrix coordinate complex general".into()))]
+    #[case(Format::MatrixMarket, "%%MatrixMarket matrix coordinate real hermitian\n2 2 1\n2 1 1.0\n", 1, 1, ParseErrorKind::InvalidHeader("%%MatrixMarket matrix coordinate real hermitian".into()))]
+    #[case(Format::MatrixMarket, "%%MatrixMarket matrix coordinate pattern\n2 2 1\n2 1\n", 1, 1, ParseErrorKind::InvalidHeader("%%MatrixMarket matrix coordinate pattern".into()))]
+    #[case(Format::MatrixMarket, "%%MatrixMarket matrix coordinate pattern general\n2 3 0\n", 2, 1, ParseErrorKind::InvalidHeader("matrix is 2x3, not square".into()))]
+    #[case(Format::Pace, "1 2\n", 1, 1, ParseErrorKind::MissingProblemLine)]
+    #[case(Format::GraphMl, "<graphml><graph><node id=\"a\"/>\n  <edge source=\"a\" target=\"z\"/></graph></graphml>", 2, 3, ParseErrorKind::UnknownNode("z".into()))]
+    fn test_errors(
+        #[case] format: Format,
+        #[case] content: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] kind: ParseErrorKind,
+    ) {
+        match read_graph_from(content.as_bytes(), Some(format)) {
+            Err(GraphErr::ParseError {
+                line: l,
+                column: c,
+                kind: k,
+            }) => assert_eq!((l, c, k), (line, column, kind)),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    // 声明的节点数超过上限时在分配之前报错
+    #[rstest]
+    #[case(Format::Dimacs, "p edge 4000000000 0\n", 8)]
+    #[case(Format::Metis, "4000000000 0\n\n\n\n\n\n\n\n\n\n\n\n", 1)]
+    #[case(
+        Format::MatrixMarket,
+        "%%MatrixMarket matrix coordinate pattern symmetric\n4000000000 4000000000 0\n",
+        1
+    )]
+    #[case(Format::Pace, "p tw 4000000000 0\n", 6)]
+    fn test_max_vertices(#[case] format: Format, #[case] content: &str, #[case] column: usize) {
+        for options in [ReadOptions::new(), ReadOptions::new().max_vertices(10)] {
+            match read_graph_from_with(content.as_bytes(), Some(format), &options) {
+                Err(GraphErr::ParseError {
+                    column: c, kind, ..
+                }) => assert_eq!(
+                    (c, kind),
+                    (column, ParseErrorKind::TooManyVertices(4_000_000_000))
+                ),
+                other => panic!("unexpected result: {other:?}"),
+            }
+        }
+        let small = content.replace("4000000000", "11");
+        let options = ReadOptions::new().max_vertices(10);
+        assert!(read_graph_from_with(small.as_bytes(), Some(format), &options).is_err());
+        let options = ReadOptions::new().max_vertices(11);
+        let labeled = read_graph_from_with(small.as_bytes(), Some(format), &options).unwrap();
+        assert_eq!(labeled.graph.node_count(), 11);
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use clique::ga::{
+        GAConfig, GAObserver, GenerationStats, find_all_max_cliques_with_ga,
+        find_max_cliques_with_ga, find_max_cliques_with_ga_config,
+        find_max_cliques_with_ga_observed, find_top_k_cliques_with_ga,
+    };
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::Algorithm;
+    use clique::solver::{SolverOptions, solve};
+    use petgraph::graph::UnGraph;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("data/brock200_2.clq")]
+    #[case("data/C250.9.clq")]
+    #[case("data/keller4.clq")]
+    fn test_same_seed_same_clique(#[case] path: &str) {
+        let graph = read_dimacs(path).unwrap();
+        let config = GAConfig::new().max_generations(50).seed(7);
+        let first = find_max_cliques_with_ga_config(&graph, &config);
+        let second = find_max_cliques_with_ga_config(&graph, &config);
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = UnGraph::<(), ()>::new_undirected();
+        assert!(find_max_cliques_with_ga(&graph).is_empty());
+        assert!(find_all_max_cliques_with_ga(&graph).is_empty());
+        assert!(find_top_k_cliques_with_ga(&graph, 3).is_empty());
+    }
+
+    #[test]
+    fn test_seed_independent_of_threads() {
+        let graph = read_dimacs("data/C250.9.clq").unwrap();
+        let config = GAConfig::new().max_generations(50).seed(11);
+        let single = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap()
+            .install(|| find_max_cliques_with_ga_config(&graph, &config));
+        let multi = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap()
+            .install(|| find_max_cliques_with_ga_config(&graph, &config));
+        assert_eq!(single, multi);
+    }
+
+    #[test]
+    fn test_solver_uses_ga_config() {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let config = GAConfig::new().population_size(20).seed(3);
+        let options = SolverOptions::new()
+            .algorithm(Algorithm::Genetic)
+            .ga_config(config.clone());
+        assert_eq!(
+            solve(&graph, &options).clique,
+            find_max_cliques_with_ga_config(&graph, &config)
+        );
+    }
+
+    #[test]
+    fn test_population_size_clamped() {
+        assert_eq!(
+            GAConfig::new().population_size(0),
+            GAConfig::new().population_size(2)
+        );
+        assert_eq!(GAConfig::default(), GAConfig::new());
+    }
+
+    #[test]
+    fn test_observer_called_each_generation() {
+        let graph = read_dimacs("data/C125.9.clq").unwrap();
+        let config = GAConfig::new()
+            .population_size(12)
+            .max_generations(40)
+            .shuffle_tolerance(3)
+            .seed(5);
+        let mut history: Vec<GenerationStats> = Vec::new();
+        let clique =
+            find_max_cliques_with_ga_observed(&graph, &config, &mut |stats: &GenerationStats| {
+                history.push(stats.clone())
+            });
+
+        assert_eq!(history.len(), 40);
+        for (i, stats) in history.iter().enumerate() {
+            assert_eq!(stats.generation, i + 1);
+            assert!(!stats.population_sizes.is_empty());
+            assert!(stats.stagnation_counter < 3);
+            assert!(stats.population_sizes.iter().all(|&s| s <= stats.best_size));
+        }
+        assert!(history.windows(2).all(|w| w[0].best_size <= w[1].best_size));
+        assert_eq!(history.last().unwrap().best_size, clique.len());
+        assert!(history.iter().any(|s| s.reshuffled));
+
+        // 观察者不影响搜索结果
+        assert_eq!(clique, find_max_cliques_with_ga_config(&graph, &config));
+    }
+
+    struct Counter(usize);
+
+    impl GAObserver for Counter {
+        fn on_generation(&mut self, _: &GenerationStats) {
+            self.0 += 1;
+        }
+    }
+
+    #[test]
+    fn test_observer_struct() {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let mut counter = Counter(0);
+        find_max_cliques_with_ga_observed(
+            &graph,
+            &GAConfig::new().max_generations(7),
+            &mut counter,
+        );
+        assert_eq!(counter.0, 7);
+    }
+}
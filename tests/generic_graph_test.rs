@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use clique::constraints::{ConstraintError, Constraints, find_max_clique_with_by};
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::{Algorithm, find_max_cliques, find_max_cliques_by, maximal_cliques};
+    use clique::preprocess::{core_numbers, reduce, reduce_with_greedy};
+    use clique::solver::{SolveStatus, SolverOptions, solve};
+    use clique::verify::{VerifyError, check_clique};
+    use petgraph::csr::Csr;
+    use petgraph::graph::UnGraph;
+    use petgraph::graphmap::UnGraphMap;
+    use petgraph::stable_graph::{NodeIndex, StableUnGraph};
+    use rstest::rstest;
+
+    #[derive(Debug)]
+    struct Protein {
+        name: String,
+    }
+
+    // 每个真实节点前插入一个随后被删除的节点，NodeIndex 不再连续
+    fn stable_graph(graph: &UnGraph<(), ()>) -> (StableUnGraph<Protein, f32>, Vec<NodeIndex>) {
+        let mut stable = StableUnGraph::default();
+        let (mut nodes, mut holes) = (Vec::new(), Vec::new());
+        for u in graph.node_indices() {
+            holes.push(stable.add_node(Protein {
+                name: "removed".into(),
+            }));
+            nodes.push(stable.add_node(Protein {
+                name: format!("p{}", u.index()),
+            }));
+        }
+        for hole in holes {
+            stable.remove_node(hole);
+        }
+        for edge in graph.raw_edges() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            stable.add_edge(nodes[a], nodes[b], 0.5);
+        }
+        (stable, nodes)
+    }
+
+    fn graph_map(graph: &UnGraph<(), ()>) -> UnGraphMap<u32, f32> {
+        let id = |u: usize| 1000 + 7 * u as u32;
+        let mut map = UnGraphMap::new();
+        for u in graph.node_indices() {
+            map.add_node(id(u.index()));
+        }
+        for edge in graph.raw_edges() {
+            map.add_edge(id(edge.source().index()), id(edge.target().index()), 1.0);
+        }
+        map
+    }
+
+    #[rstest]
+    #[case(Algorithm::Auto)]
+    #[case(Algorithm::BronKerbosch)]
+    #[case(Algorithm::Mcs)]
+    #[case(Algorithm::Sparse)]
+    #[case(Algorithm::Portfolio)]
+    fn test_stable_graph_with_removed_nodes(#[case] algorithm: Algorithm) {
+        let graph = read_dimacs("data/brock200_4.clq").unwrap();
+        let (stable, _) = stable_graph(&graph);
+
+        let clique = find_max_cliques_by(&stable, algorithm);
+        assert_eq!(clique.len(), 17);
+        assert!(clique.iter().all(|&u| stable[u].name.starts_with('p')));
+        assert_eq!(check_clique(&stable, &clique), Ok(()));
+
+        // 进度回调中的节点是 to_index 编号，跳过被删除的位置
+        let last = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let options = {
+            let last = last.clone();
+            SolverOptions::new()
+                .algorithm(algorithm)
+                .on_progress(move |progress| *last.lock().unwrap() = progress.clique.clone())
+        };
+        let solution = solve(&stable, &options);
+        let mut reported: Vec<NodeIndex> = last
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|&u| NodeIndex::new(u))
+            .collect();
+        let mut clique = solution.clique;
+        reported.sort();
+        clique.sort();
+        assert_eq!(reported, clique);
+    }
+
+    #[test]
+    fn test_graph_map_ids() {
+        let graph = read_dimacs("data/brock200_4.clq").unwrap();
+        let map = graph_map(&graph);
+
+        let clique = find_max_cliques(&map);
+        assert_eq!(clique.len(), 17);
+        assert!(clique.iter().all(|&u| u >= 1000 && (u - 1000) % 7 == 0));
+        assert_eq!(check_clique(&map, &clique), Ok(()));
+
+        let solution = solve(&map, &SolverOptions::new().algorithm(Algorithm::Mcs));
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.clique.len(), 17);
+    }
+
+    #[test]
+    fn test_directed_csr_as_undirected() {
+        // 三角形 0-1-2 和 2-3，每条边只存一个方向
+        let csr = Csr::<(), ()>::from_sorted_edges(&[(0, 1), (0, 2), (1, 2), (3, 2)]).unwrap();
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 2), (3, 2)]);
+
+        let mut clique = find_max_cliques(&csr);
+        clique.sort();
+        assert_eq!(clique, vec![0u32, 1, 2]);
+
+        let sorted = |cliques: Vec<Vec<u32>>| {
+            let mut cliques: Vec<_> = cliques
+                .into_iter()
+                .map(|mut c| {
+                    c.sort();
+                    c
+                })
+                .collect();
+            cliques.sort();
+            cliques
+        };
+        let expected = maximal_cliques(&graph)
+            .map(|c| c.iter().map(|u| u.index() as u32).collect())
+            .collect();
+        assert_eq!(sorted(maximal_cliques(&csr).collect()), sorted(expected));
+        assert_eq!(
+            check_clique(&csr, &[3, 1]),
+            Err(VerifyError::NotAdjacent(3, 1))
+        );
+    }
+
+    #[test]
+    fn test_constraints_with_graph_map() {
+        let map = UnGraphMap::<&str, ()>::from_edges([
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "c"),
+            ("e", "f"),
+        ]);
+        let constraints = Constraints::new().include(["d"]);
+        let mut clique = find_max_clique_with_by(&map, &constraints, Algorithm::Mcs).unwrap();
+        clique.sort();
+        assert_eq!(clique, vec!["c", "d", "e"]);
+
+        let constraints = Constraints::new().include(["a", "d"]);
+        assert_eq!(
+            find_max_clique_with_by(&map, &constraints, Algorithm::BronKerbosch),
+            Err(ConstraintError::Infeasible(VerifyError::NotAdjacent(
+                "a", "d"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_preprocess_keeps_caller_ids() {
+        // 4-团 {0,1,2,3} 加一条悬挂路径 3-4-5
+        let graph = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+        ]);
+        let (stable, nodes) = stable_graph(&graph);
+
+        // 核数按 NodeIndex 索引，被删除的位置为 0
+        let core = core_numbers(&stable);
+        assert_eq!(core.len(), 12);
+        assert_eq!(
+            nodes.iter().map(|u| core[u.index()]).collect::<Vec<_>>(),
+            vec![3, 3, 3, 3, 1, 1]
+        );
+
+        // 贪心下界已是最优解，约简图为空
+        let reduction = reduce_with_greedy(&stable);
+        assert_eq!(reduction.lower_bound.len(), 4);
+        assert!(reduction.lower_bound.iter().all(|u| nodes[..4].contains(u)));
+        assert_eq!(reduction.graph.node_count(), 0);
+
+        let reduction = reduce(&stable, nodes[..3].to_vec());
+        assert_eq!(reduction.original, nodes[..4]);
+        assert_eq!(reduction.best_of(&[]), nodes[..3]);
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::{
+        DimacsOptions, read_dimacs, read_dimacs_file, read_dimacs_file_from, read_dimacs_from,
+    };
+    use clique::graph_writer::{
+        write_dimacs, write_dot, write_edge_list, write_graphml, write_metis,
+    };
+    use clique::max_clique::find_max_cliques;
+    use petgraph::graph::{NodeIndex, UnGraph};
+    use rstest::rstest;
+
+    fn to_string(write: impl FnOnce(&mut Vec<u8>)) -> String {
+        let mut out = Vec::new();
+        write(&mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    // 0-1-2 构成三角形，外加自环 3-3 和重复边 1-0
+    fn triangle() -> UnGraph<(), ()> {
+        UnGraph::from_edges([(0, 1), (1, 2), (0, 2), (2, 3), (3, 3), (1, 0)])
+    }
+
+    #[rstest]
+    #[case("data/brock200_2.clq")]
+    #[case("data/MANN_a27.clq")]
+    fn test_dimacs_round_trip(#[case] path: &str) {
+        let file = read_dimacs_file(path, &DimacsOptions::new()).unwrap();
+        assert!(!file.comments.is_empty());
+        let text = to_string(|out| write_dimacs(&file.graph, &file.comments, out).unwrap());
+
+        let again = read_dimacs_file_from(text.as_bytes(), &DimacsOptions::new()).unwrap();
+        assert_eq!(again.comments, file.comments);
+        assert_eq!(again.graph.node_count(), file.graph.node_count());
+        assert_eq!(again.graph.edge_count(), file.graph.edge_count());
+        assert_eq!(
+            find_max_cliques(&again.graph).len(),
+            find_max_cliques(&file.graph).len()
+        );
+    }
+
+    #[test]
+    fn test_dimacs_skips_loops_and_duplicates() {
+        let text = to_string(|out| write_dimacs(&triangle(), &["hello".into()], out).unwrap());
+        assert_eq!(text, "c hello\np edge 4 4\ne 1 2\ne 2 3\ne 1 3\ne 3 4\n");
+        assert_eq!(read_dimacs_from(text.as_bytes()).unwrap().edge_count(), 4);
+    }
+
+    #[test]
+    fn test_edge_list() {
+        let text = to_string(|out| write_edge_list(&triangle(), out).unwrap());
+        assert_eq!(text, "0 1\n1 2\n0 2\n2 3\n");
+    }
+
+    #[test]
+    fn test_metis() {
+        let text = to_string(|out| write_metis(&triangle(), out).unwrap());
+        assert_eq!(text, "4 4\n2 3\n1 3\n1 2 4\n3\n");
+    }
+
+    #[test]
+    fn test_graphml() {
+        let text = to_string(|out| write_graphml(&triangle(), out).unwrap());
+        assert!(text.contains(r#"edgedefault="undirected""#));
+        assert_eq!(text.matches("<node ").count(), 4);
+        assert_eq!(text.matches("<edge ").count(), 4);
+        assert!(text.contains(r#"<edge source="n2" target="n3"/>"#));
+    }
+
+    #[test]
+    fn test_dot_highlights_clique() {
+        let graph = read_dimacs("data/small.clq").unwrap();
+        let clique = find_max_cliques(&graph);
+        let text = to_string(|out| write_dot(&graph, &clique, out).unwrap());
+
+        assert!(text.starts_with("graph G {"));
+        // 三个节点和三条边带有 clique 属性
+        assert_eq!(text.matches("clique=true").count(), 6);
+        for u in &clique {
+            assert!(text.contains(&format!("  {} [clique=true", u.index())));
+        }
+        let empty = to_string(|out| write_dot(&graph, &[] as &[NodeIndex], out).unwrap());
+        assert!(!empty.contains("clique=true"));
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::{for_each_maximal_clique, maximal_cliques};
+    use petgraph::graph::{NodeIndex, UnGraph};
+    use std::ops::ControlFlow;
+
+    fn sorted(mut cliques: Vec<Vec<NodeIndex>>) -> Vec<Vec<usize>> {
+        let mut cliques: Vec<Vec<usize>> = cliques
+            .iter_mut()
+            .map(|c| {
+                let mut c: Vec<usize> = c.iter().map(|n| n.index()).collect();
+                c.sort();
+                c
+            })
+            .collect();
+        cliques.sort();
+        cliques
+    }
+
+    #[test]
+    fn test_small() {
+        let graph = read_dimacs("data/small.clq").unwrap();
+        let cliques = sorted(maximal_cliques(&graph).collect());
+        assert_eq!(cliques, vec![vec![0, 1, 3], vec![1, 2], vec![1, 3, 4]]);
+    }
+
+    #[test]
+    fn test_isolated_nodes() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_node(());
+        graph.add_edge(a, b, ());
+        let cliques = sorted(maximal_cliques(&graph).collect());
+        assert_eq!(cliques, vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn test_all_maximal() {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let mut count = 0;
+        for clique in maximal_cliques(&graph) {
+            for (i, &u) in clique.iter().enumerate() {
+                for &v in &clique[i + 1..] {
+                    assert!(graph.contains_edge(u, v));
+                }
+            }
+            // 没有节点能与整个团相连
+            assert!(graph.node_indices().all(|w| {
+                clique.contains(&w) || clique.iter().any(|&u| !graph.contains_edge(u, w))
+            }));
+            count += 1;
+            if count == 500 {
+                break;
+            }
+        }
+        assert_eq!(count, 500);
+    }
+
+    #[test]
+    fn test_early_stop() {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let mut seen = 0;
+        let stopped = for_each_maximal_clique(&graph, |_| {
+            seen += 1;
+            if seen == 10 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert!(stopped);
+        assert_eq!(seen, 10);
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::{Algorithm, find_max_cliques_by};
+    use clique::mcs::{find_max_cliques_with_mcs, find_max_cliques_with_parallel_mcs};
+    use petgraph::graph::UnGraph;
+    use rand::prelude::*;
+    use rstest::rstest;
+
+    fn assert_clique(graph: &UnGraph<(), ()>, clique: &[petgraph::graph::NodeIndex]) {
+        for (i, &u) in clique.iter().enumerate() {
+            for &v in &clique[i + 1..] {
+                assert!(graph.contains_edge(u, v));
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::small("small.clq", 3)]
+    #[case::brock200_4("brock200_4.clq", 17)]
+    #[case::C125_9("C125.9.clq", 34)]
+    #[case::hamming8_4("hamming8-4.clq", 16)]
+    #[case::keller4("keller4.clq", 11)]
+    #[case::p_hat300_2("p_hat300-2.clq", 25)]
+    #[case::MANN_a27("MANN_a27.clq", 126)]
+    #[allow(non_snake_case)]
+    fn test_mcs(#[case] filename: &str, #[case] expected_size: usize) {
+        let graph = read_dimacs(&format!("data/{}", filename)).unwrap();
+        let clique = find_max_cliques_with_mcs(&graph);
+        assert_eq!(clique.len(), expected_size);
+        assert_clique(&graph, &clique);
+    }
+
+    #[test]
+    fn test_matches_bk_on_random_graphs() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..30 {
+            let n = rng.random_range(1..40);
+            let p = rng.random_range(0.1..0.9);
+            let mut graph = UnGraph::<(), ()>::new_undirected();
+            let nodes: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+            for i in 0..n {
+                for j in i + 1..n {
+                    if rng.random_bool(p) {
+                        graph.add_edge(nodes[i], nodes[j], ());
+                    }
+                }
+            }
+            let bk = find_max_cliques_by(&graph, Algorithm::BronKerbosch);
+            let mcs = find_max_cliques_by(&graph, Algorithm::Mcs);
+            assert_eq!(bk.len(), mcs.len());
+            assert_clique(&graph, &mcs);
+            let parallel = find_max_cliques_by(&graph, Algorithm::ParallelMcs);
+            assert_eq!(bk.len(), parallel.len());
+            assert_clique(&graph, &parallel);
+        }
+    }
+
+    #[test]
+    fn test_parallel_mcs() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        for (filename, expected_size) in [("brock200_4.clq", 17), ("p_hat300-2.clq", 25)] {
+            let graph = read_dimacs(&format!("data/{}", filename)).unwrap();
+            let clique = pool.install(|| find_max_cliques_with_parallel_mcs(&graph));
+            assert_eq!(clique.len(), expected_size);
+            assert_clique(&graph, &clique);
+        }
+    }
+
+    #[test]
+    fn test_auto_prefers_exact() {
+        let graph = read_dimacs("data/p_hat300-3.clq").unwrap();
+        assert!(Algorithm::Auto.resolve(&graph).is_exact());
+        for filename in ["brock400_2.clq", "brock400_4.clq"] {
+            let graph = read_dimacs(&format!("data/{}", filename)).unwrap();
+            assert!(Algorithm::Auto.resolve(&graph).is_exact(), "{filename}");
+        }
+        let graph = read_dimacs("data/keller5.clq").unwrap();
+        assert_eq!(Algorithm::Auto.resolve(&graph), Algorithm::Genetic);
+        assert!(!Algorithm::Genetic.is_exact());
+    }
+}
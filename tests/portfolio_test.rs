@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::Algorithm;
+    use clique::portfolio::find_max_cliques_with_portfolio;
+    use clique::solver::{CancelToken, SolveStatus, SolverOptions, solve};
+    use clique::verify::check_clique;
+    use petgraph::graph::UnGraph;
+    use rand::prelude::*;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[rstest]
+    #[case::small("small.clq", 3)]
+    #[case::brock200_4("brock200_4.clq", 17)]
+    #[case::C125_9("C125.9.clq", 34)]
+    #[case::hamming8_4("hamming8-4.clq", 16)]
+    #[case::keller4("keller4.clq", 11)]
+    #[allow(non_snake_case)]
+    fn test_portfolio_proves_optimum(#[case] filename: &str, #[case] expected_size: usize) {
+        let graph = read_dimacs(&format!("data/{}", filename)).unwrap();
+        let solution = solve(
+            &graph,
+            &SolverOptions::new().algorithm(Algorithm::Portfolio),
+        );
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.algorithm, Algorithm::Portfolio);
+        assert_eq!(solution.clique.len(), expected_size);
+        assert_eq!(check_clique(&graph, &solution.clique), Ok(()));
+    }
+
+    #[test]
+    fn test_matches_mcs_on_random_graphs() {
+        let mut rng = StdRng::seed_from_u64(29);
+        for _ in 0..10 {
+            let n = rng.random_range(1..40);
+            let p = rng.random_range(0.1..0.9);
+            let mut graph = UnGraph::<(), ()>::new_undirected();
+            let nodes: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+            for i in 0..n {
+                for j in i + 1..n {
+                    if rng.random_bool(p) {
+                        graph.add_edge(nodes[i], nodes[j], ());
+                    }
+                }
+            }
+            let mcs = solve(&graph, &SolverOptions::new().algorithm(Algorithm::Mcs));
+            let portfolio = find_max_cliques_with_portfolio(&graph);
+            assert_eq!(mcs.clique.len(), portfolio.len());
+            assert_eq!(check_clique(&graph, &portfolio), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_portfolio_deadline() {
+        // 精确求解需要很久，到达截止时间时返回遗传算法目前的最优解
+        let graph = read_dimacs("data/C250.9.clq").unwrap();
+        let options = SolverOptions::new()
+            .algorithm(Algorithm::Portfolio)
+            .time_limit(Duration::from_millis(500));
+        let solution = solve(&graph, &options);
+        assert_eq!(solution.status, SolveStatus::TimedOut);
+        assert!(solution.clique.len() >= 35);
+        assert_eq!(check_clique(&graph, &solution.clique), Ok(()));
+        assert!(solution.elapsed < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_portfolio_cancel() {
+        let graph = read_dimacs("data/C250.9.clq").unwrap();
+        let token = CancelToken::new();
+        token.cancel();
+        let options = SolverOptions::new()
+            .algorithm(Algorithm::Portfolio)
+            .cancel_token(token);
+        let solution = solve(&graph, &options);
+        assert_eq!(solution.status, SolveStatus::Cancelled);
+        assert_eq!(check_clique(&graph, &solution.clique), Ok(()));
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::{Algorithm, find_max_cliques};
+    use clique::preprocess::{Preprocess, core_numbers, greedy_clique, reduce, reduce_with_greedy};
+    use clique::solver::{SolveStatus, SolverOptions, solve};
+    use clique::verify::check_clique;
+    use petgraph::graph::{NodeIndex, UnGraph};
+    use rand::prelude::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_core_numbers() {
+        // 4-团 {0,1,2,3}，4 挂在 3 上，5-6-7 成环，8 孤立
+        let mut graph = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (5, 6),
+            (6, 7),
+            (7, 5),
+        ]);
+        graph.add_node(());
+        assert_eq!(core_numbers(&graph), vec![3, 3, 3, 3, 1, 2, 2, 2, 0]);
+        assert_eq!(greedy_clique(&graph).len(), 4);
+
+        // 下界已经最优时约简图为空
+        assert_eq!(reduce_with_greedy(&graph).graph.node_count(), 0);
+
+        let triangle = vec![NodeIndex::new(5), NodeIndex::new(6), NodeIndex::new(7)];
+        let reduction = reduce(&graph, triangle);
+        assert_eq!(reduction.graph.node_count(), 4);
+        assert_eq!(reduction.graph.edge_count(), 6);
+        let all: Vec<_> = reduction.graph.node_indices().collect();
+        let mut mapped = reduction.map_back(&all);
+        mapped.sort();
+        assert_eq!(mapped, (0..4).map(NodeIndex::new).collect::<Vec<_>>());
+    }
+
+    #[rstest]
+    #[case::brock200_4("brock200_4.clq", 17)]
+    #[case::C125_9("C125.9.clq", 34)]
+    #[case::hamming8_4("hamming8-4.clq", 16)]
+    #[case::keller4("keller4.clq", 11)]
+    #[case::p_hat300_2("p_hat300-2.clq", 25)]
+    #[allow(non_snake_case)]
+    fn test_preprocessed_solve(#[case] filename: &str, #[case] expected_size: usize) {
+        let graph = read_dimacs(&format!("data/{}", filename)).unwrap();
+        let greedy = greedy_clique(&graph);
+        assert!(!greedy.is_empty() && greedy.len() <= expected_size);
+        assert_eq!(check_clique(&graph, &greedy), Ok(()));
+
+        let options = SolverOptions::new()
+            .algorithm(Algorithm::Mcs)
+            .preprocess(Preprocess::Greedy);
+        let solution = solve(&graph, &options);
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.clique.len(), expected_size);
+        assert_eq!(check_clique(&graph, &solution.clique), Ok(()));
+    }
+
+    #[test]
+    fn test_matches_unreduced_on_random_graphs() {
+        let mut rng = StdRng::seed_from_u64(23);
+        for _ in 0..30 {
+            let n = rng.random_range(1..50);
+            let p = rng.random_range(0.05..0.6);
+            let mut graph = UnGraph::<(), ()>::new_undirected();
+            let nodes: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+            for i in 0..n {
+                for j in i + 1..n {
+                    if rng.random_bool(p) {
+                        graph.add_edge(nodes[i], nodes[j], ());
+                    }
+                }
+            }
+            let options = SolverOptions::new().algorithm(Algorithm::Mcs);
+            let plain = solve(&graph, &options);
+            let reduced = solve(&graph, &options.preprocess(Preprocess::Greedy));
+            assert_eq!(plain.clique.len(), reduced.clique.len());
+            assert_eq!(reduced.status, SolveStatus::Optimal);
+            assert_eq!(check_clique(&graph, &reduced.clique), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_reduction_shrinks_sparse_graph() {
+        // 稀疏随机图上植入一个 10-团，约简后只剩很少的节点
+        let mut rng = StdRng::seed_from_u64(3);
+        let n = 20_000;
+        let mut graph = UnGraph::<(), ()>::with_capacity(n, 2 * n);
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for _ in 0..2 * n {
+            let (a, b) = (rng.random_range(0..n), rng.random_range(0..n));
+            graph.add_edge(NodeIndex::new(a), NodeIndex::new(b), ());
+        }
+        for a in 0..10 {
+            for b in a + 1..10 {
+                graph.add_edge(NodeIndex::new(a * 97), NodeIndex::new(b * 97), ());
+            }
+        }
+
+        let reduction = reduce_with_greedy(&graph);
+        assert_eq!(reduction.lower_bound.len(), 10);
+        assert!(reduction.graph.node_count() < n / 10);
+        assert_eq!(find_max_cliques(&graph).len(), 10);
+    }
+
+    #[test]
+    fn test_genetic_lower_bound() {
+        let graph = read_dimacs("data/keller4.clq").unwrap();
+        let options = SolverOptions::new()
+            .algorithm(Algorithm::Mcs)
+            .preprocess(Preprocess::Genetic);
+        let solution = solve(&graph, &options);
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.clique.len(), 11);
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = UnGraph::<(), ()>::new_undirected();
+        assert!(greedy_clique(&graph).is_empty());
+        assert_eq!(reduce_with_greedy(&graph).graph.node_count(), 0);
+        assert!(find_max_cliques(&graph).is_empty());
+    }
+}
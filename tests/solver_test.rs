@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::Algorithm;
+    use clique::preprocess::Preprocess;
+    use clique::solver::{CancelToken, Progress, SolveStatus, SolverOptions, solve};
+    use petgraph::graph::{NodeIndex, UnGraph};
+    use rstest::rstest;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    fn assert_clique(graph: &UnGraph<(), ()>, clique: &[NodeIndex]) {
+        for (i, &u) in clique.iter().enumerate() {
+            for &v in &clique[i + 1..] {
+                assert!(graph.contains_edge(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_optimal() {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let solution = solve(&graph, &SolverOptions::new());
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert!(solution.algorithm.is_exact());
+        assert_eq!(solution.clique.len(), 12);
+        assert_eq!(solution.upper_bound, Some(12));
+    }
+
+    #[rstest]
+    fn test_empty_graph(
+        #[values(Preprocess::Off, Preprocess::Greedy, Preprocess::Genetic)] preprocess: Preprocess,
+        #[values(Algorithm::Auto, Algorithm::Genetic, Algorithm::Portfolio)] algorithm: Algorithm,
+    ) {
+        let graph = UnGraph::<(), ()>::new_undirected();
+        let options = SolverOptions::new()
+            .preprocess(preprocess)
+            .algorithm(algorithm);
+        let solution = solve(&graph, &options);
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert!(solution.clique.is_empty());
+        assert_eq!(solution.upper_bound, Some(0));
+    }
+
+    #[rstest]
+    #[case::bk(Algorithm::BronKerbosch)]
+    #[case::mcs(Algorithm::Mcs)]
+    #[case::parallel_mcs(Algorithm::ParallelMcs)]
+    #[case::genetic(Algorithm::Genetic)]
+    fn test_time_limit(#[case] algorithm: Algorithm) {
+        let graph = read_dimacs("data/keller5.clq").unwrap();
+        let start = Instant::now();
+        let solution = solve(
+            &graph,
+            &SolverOptions::new()
+                .algorithm(algorithm)
+                .time_limit(Duration::from_millis(200)),
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(solution.status, SolveStatus::TimedOut);
+        assert_eq!(solution.algorithm, algorithm);
+        assert_clique(&graph, &solution.clique);
+        // 没有证明最优时为退化度上界，keller5 的最大团规模为 27
+        let upper_bound = solution.upper_bound.unwrap();
+        assert!(upper_bound >= solution.clique.len() && upper_bound > 27);
+    }
+
+    #[test]
+    fn test_huge_time_limit() {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let options = SolverOptions::new().time_limit(Duration::MAX);
+        assert_eq!(options.deadline, None);
+        let solution = solve(&graph, &options);
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert_eq!(solution.clique.len(), 12);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let graph = read_dimacs("data/keller5.clq").unwrap();
+        let token = CancelToken::new();
+        let canceller = {
+            let token = token.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(100));
+                token.cancel();
+            })
+        };
+        let solution = solve(
+            &graph,
+            &SolverOptions::new()
+                .algorithm(Algorithm::Mcs)
+                .cancel_token(token),
+        );
+        canceller.join().unwrap();
+        assert_eq!(solution.status, SolveStatus::Cancelled);
+        assert!(!solution.clique.is_empty());
+        assert_clique(&graph, &solution.clique);
+    }
+
+    #[rstest]
+    #[case::bk(Algorithm::BronKerbosch, Preprocess::Off)]
+    #[case::mcs(Algorithm::Mcs, Preprocess::Off)]
+    #[case::parallel_mcs(Algorithm::ParallelMcs, Preprocess::Greedy)]
+    #[case::sparse(Algorithm::Sparse, Preprocess::Off)]
+    #[case::portfolio(Algorithm::Portfolio, Preprocess::Off)]
+    #[case::genetic(Algorithm::Genetic, Preprocess::Off)]
+    #[case::mcs_after_ga(Algorithm::Mcs, Preprocess::Genetic)]
+    fn test_progress(#[case] algorithm: Algorithm, #[case] preprocess: Preprocess) {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let events = Arc::new(Mutex::new(Vec::<Progress>::new()));
+        let options = {
+            let events = events.clone();
+            SolverOptions::new()
+                .algorithm(algorithm)
+                .preprocess(preprocess)
+                .on_progress(move |progress| events.lock().unwrap().push(progress.clone()))
+        };
+        let solution = solve(&graph, &options);
+        let events = events.lock().unwrap();
+
+        assert!(!events.is_empty());
+        for (i, event) in events.iter().enumerate() {
+            let clique: Vec<NodeIndex> = event.clique.iter().map(|&u| NodeIndex::new(u)).collect();
+            assert_clique(&graph, &clique);
+            assert!(event.clique.len() <= event.upper_bound);
+            if i > 0 {
+                assert!(event.elapsed >= events[i - 1].elapsed);
+                assert!(event.upper_bound <= events[i - 1].upper_bound);
+            }
+        }
+        // 证明最优时最后一次回调的上界等于解的规模，之前每次回调的团严格变大
+        let last = events.last().unwrap();
+        assert_eq!(last.clique.len(), solution.clique.len());
+        if solution.status.is_optimal() {
+            assert_eq!(last.upper_bound, 12);
+            let sizes: Vec<usize> = events[..events.len() - 1]
+                .iter()
+                .map(|e| e.clique.len())
+                .collect();
+            assert!(sizes.windows(2).all(|w| w[0] < w[1]));
+        } else {
+            assert!(
+                events
+                    .windows(2)
+                    .all(|w| w[0].clique.len() < w[1].clique.len())
+            );
+        }
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::{Algorithm, find_max_cliques_by};
+    use clique::mcs::find_max_cliques_with_mcs;
+    use clique::solver::{SolverOptions, solve};
+    use clique::sparse::find_max_cliques_with_sparse;
+    use clique::verify::check_clique;
+    use petgraph::graph::{NodeIndex, UnGraph};
+    use rand::prelude::*;
+    use rstest::rstest;
+
+    // n 个节点、平均度数约为 degree 的随机稀疏图，再在随机选出的节点上植入规模为 planted 的团
+    fn sparse_graph(n: usize, degree: usize, planted: usize, seed: u64) -> UnGraph<(), ()> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut graph = UnGraph::<(), ()>::with_capacity(n, n * degree / 2);
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for _ in 0..n * degree / 2 {
+            let (a, b) = (rng.random_range(0..n), rng.random_range(0..n));
+            graph.add_edge(NodeIndex::new(a), NodeIndex::new(b), ());
+        }
+        let members: Vec<usize> = (0..n).choose_multiple(&mut rng, planted);
+        for (i, &a) in members.iter().enumerate() {
+            for &b in &members[i + 1..] {
+                graph.add_edge(NodeIndex::new(a), NodeIndex::new(b), ());
+            }
+        }
+        graph
+    }
+
+    #[rstest]
+    #[case::small("small.clq", 3)]
+    #[case::brock200_4("brock200_4.clq", 17)]
+    #[case::C125_9("C125.9.clq", 34)]
+    #[case::hamming8_4("hamming8-4.clq", 16)]
+    #[case::keller4("keller4.clq", 11)]
+    #[case::p_hat300_2("p_hat300-2.clq", 25)]
+    #[allow(non_snake_case)]
+    fn test_sparse(#[case] filename: &str, #[case] expected_size: usize) {
+        let graph = read_dimacs(&format!("data/{}", filename)).unwrap();
+        let clique = find_max_cliques_with_sparse(&graph);
+        assert_eq!(clique.len(), expected_size);
+        assert_eq!(check_clique(&graph, &clique), Ok(()));
+    }
+
+    #[test]
+    fn test_matches_mcs_on_random_graphs() {
+        let mut rng = StdRng::seed_from_u64(17);
+        for _ in 0..30 {
+            let n = rng.random_range(1..60);
+            let degree = rng.random_range(0..12);
+            let planted = rng.random_range(0..n.min(8));
+            let graph = sparse_graph(n, degree, planted, rng.random());
+            let mcs = find_max_cliques_with_mcs(&graph);
+            let sparse = find_max_cliques_with_sparse(&graph);
+            assert_eq!(mcs.len(), sparse.len());
+            assert_eq!(check_clique(&graph, &sparse), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_self_loops_and_isolated_nodes() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        graph.add_edge(nodes[0], nodes[0], ());
+        graph.add_edge(nodes[1], nodes[2], ());
+        graph.add_edge(nodes[2], nodes[1], ());
+        let clique = find_max_cliques_with_sparse(&graph);
+        assert_eq!(clique.len(), 2);
+        assert_eq!(check_clique(&graph, &clique), Ok(()));
+
+        assert!(find_max_cliques_with_sparse(&UnGraph::<(), ()>::new_undirected()).is_empty());
+    }
+
+    #[test]
+    fn test_large_sparse_graph() {
+        // 邻接矩阵需要 200000² 位（约 5GB），稀疏算法只用邻接表
+        let graph = sparse_graph(200_000, 8, 12, 5);
+        let clique = find_max_cliques_with_sparse(&graph);
+        assert!(clique.len() >= 12);
+        assert_eq!(check_clique(&graph, &clique), Ok(()));
+        assert_eq!(Algorithm::Auto.resolve(&graph), Algorithm::Sparse);
+        assert_eq!(
+            find_max_cliques_by(&graph, Algorithm::Auto).len(),
+            clique.len()
+        );
+    }
+
+    #[test]
+    fn test_solver_sparse() {
+        let graph = read_dimacs("data/keller4.clq").unwrap();
+        let result = solve(&graph, &SolverOptions::new().algorithm(Algorithm::Sparse));
+        assert_eq!(result.algorithm, Algorithm::Sparse);
+        assert!(result.status.is_optimal());
+        assert_eq!(result.clique.len(), 11);
+        assert!(Algorithm::Sparse.is_exact());
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::{find_all_max_cliques, find_max_cliques, find_top_k_cliques};
+    use petgraph::graph::UnGraph;
+
+    #[test]
+    fn test_all_max_small() {
+        let graph = read_dimacs("data/small.clq").unwrap();
+        let mut cliques: Vec<Vec<usize>> = find_all_max_cliques(&graph)
+            .iter()
+            .map(|c| {
+                let mut c: Vec<usize> = c.iter().map(|n| n.index()).collect();
+                c.sort();
+                c
+            })
+            .collect();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 1, 3], vec![1, 3, 4]]);
+    }
+
+    #[test]
+    fn test_all_max_ties() {
+        // 两个不相交的三角形加一条边
+        let graph =
+            UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2), (3, 4), (4, 5), (3, 5), (2, 3)]);
+        let cliques = find_all_max_cliques(&graph);
+        assert_eq!(cliques.len(), 2);
+        assert!(cliques.iter().all(|c| c.len() == 3));
+    }
+
+    #[test]
+    fn test_top_k_small() {
+        let graph = read_dimacs("data/small.clq").unwrap();
+        let cliques = find_top_k_cliques(&graph, 5);
+        let sizes: Vec<usize> = cliques.iter().map(|c| c.len()).collect();
+        assert_eq!(sizes, vec![3, 3, 2]);
+        assert!(find_top_k_cliques(&graph, 0).is_empty());
+    }
+
+    #[test]
+    fn test_top_k_brock200_2() {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let cliques = find_top_k_cliques(&graph, 10);
+        assert_eq!(cliques.len(), 10);
+        assert_eq!(cliques[0].len(), find_max_cliques(&graph).len());
+        assert!(cliques.windows(2).all(|w| w[0].len() >= w[1].len()));
+        for (i, a) in cliques.iter().enumerate() {
+            assert!(cliques[i + 1..].iter().all(|b| a != b));
+        }
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::max_clique::find_max_cliques;
+    use clique::mcs::find_max_cliques_with_mcs_proof;
+    use clique::verify::{Proof, VerifyError, check_clique, check_maximal, check_maximum};
+    use petgraph::graph::{NodeIndex, UnGraph};
+    use rstest::rstest;
+
+    fn nodes(ids: &[usize]) -> Vec<NodeIndex> {
+        ids.iter().map(|&i| NodeIndex::new(i)).collect()
+    }
+
+    // 0-1-2 构成三角形，3 只与 2 相连
+    fn triangle_with_tail() -> UnGraph<(), ()> {
+        UnGraph::from_edges([(0, 1), (1, 2), (0, 2), (2, 3)])
+    }
+
+    #[test]
+    fn test_check_clique() {
+        let graph = triangle_with_tail();
+        assert_eq!(check_clique(&graph, &nodes(&[0, 1, 2])), Ok(()));
+        assert_eq!(
+            check_clique(&graph, &nodes(&[0, 2, 3])),
+            Err(VerifyError::NotAdjacent(
+                NodeIndex::new(0),
+                NodeIndex::new(3)
+            ))
+        );
+        assert_eq!(
+            check_clique(&graph, &nodes(&[1, 1])),
+            Err(VerifyError::DuplicateNode(NodeIndex::new(1)))
+        );
+        assert_eq!(
+            check_clique(&graph, &nodes(&[9])),
+            Err(VerifyError::NodeOutOfRange(NodeIndex::new(9)))
+        );
+    }
+
+    #[test]
+    fn test_check_maximal() {
+        let graph = triangle_with_tail();
+        assert_eq!(check_maximal(&graph, &nodes(&[2, 3])), Ok(()));
+        assert_eq!(
+            check_maximal(&graph, &nodes(&[0, 1])),
+            Err(VerifyError::NotMaximal(NodeIndex::new(2)))
+        );
+        assert_eq!(
+            check_maximal(&graph, &[]),
+            Err(VerifyError::NotMaximal(NodeIndex::new(0)))
+        );
+    }
+
+    #[rstest]
+    #[case("data/small.clq")]
+    #[case("data/brock200_2.clq")]
+    #[case("data/C125.9.clq")]
+    #[case("data/keller4.clq")]
+    #[case("data/MANN_a27.clq")]
+    fn test_proof(#[case] path: &str) {
+        let graph = read_dimacs(path).unwrap();
+        let (clique, proof) = find_max_cliques_with_mcs_proof(&graph);
+        assert_eq!(clique.len(), find_max_cliques(&graph).len());
+        assert_eq!(check_maximum(&graph, &clique, &proof), Ok(()));
+
+        // 经过文本格式往返后仍然成立
+        let mut text = Vec::new();
+        proof.write_to(&mut text).unwrap();
+        let replayed = Proof::read_from(text.as_slice()).unwrap();
+        assert_eq!(replayed, proof);
+
+        // 去掉一个节点后证明不再成立
+        let smaller = &clique[1..];
+        assert!(matches!(
+            check_maximum(&graph, smaller, &proof),
+            Err(VerifyError::InvalidProof(_))
+        ));
+    }
+
+    #[test]
+    fn test_tampered_proof() {
+        let graph = read_dimacs("data/brock200_2.clq").unwrap();
+        let (clique, mut proof) = find_max_cliques_with_mcs_proof(&graph);
+
+        let mut truncated = proof.clone();
+        truncated.steps.pop();
+        assert!(check_maximum(&graph, &clique, &truncated).is_err());
+
+        // 把两个颜色类合并，得到的不再是独立集
+        let root = &mut proof.steps[0];
+        let class = root.classes.pop().unwrap();
+        root.classes.last_mut().unwrap().extend(class);
+        assert!(check_maximum(&graph, &clique, &proof).is_err());
+    }
+
+    #[test]
+    fn test_empty_graph_proof() {
+        let graph = UnGraph::<(), ()>::new_undirected();
+        let (clique, proof) = find_max_cliques_with_mcs_proof(&graph);
+        assert!(clique.is_empty());
+        assert_eq!(check_maximum(&graph, &clique, &proof), Ok(()));
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use clique::graph_reader::read_dimacs;
+    use clique::weighted::{
+        find_max_weight_clique, find_max_weight_clique_exact, find_max_weight_clique_with_ga,
+    };
+    use petgraph::graph::UnGraph;
+
+    // 三角形 0-1-2 的总权重小于边 3-4
+    fn profit_graph() -> UnGraph<u32, ()> {
+        let mut graph = UnGraph::new_undirected();
+        let n: Vec<_> = [1, 1, 1, 5, 4].into_iter().map(|w| graph.add_node(w)).collect();
+        for (u, v) in [(0, 1), (1, 2), (0, 2), (2, 3), (3, 4)] {
+            graph.add_edge(n[u], n[v], ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_exact() {
+        let graph = profit_graph();
+        let result = find_max_weight_clique_exact(&graph);
+        let mut nodes: Vec<usize> = result.nodes.iter().map(|n| n.index()).collect();
+        nodes.sort();
+        assert_eq!(nodes, vec![3, 4]);
+        assert_eq!(result.weight, 9);
+        assert_eq!(find_max_weight_clique(&graph), result);
+    }
+
+    #[test]
+    fn test_non_positive_weights() {
+        let mut graph = UnGraph::<f64, ()>::new_undirected();
+        let a = graph.add_node(2.5);
+        let b = graph.add_node(-1.0);
+        let c = graph.add_node(0.0);
+        graph.extend_with_edges([(a, b), (b, c), (a, c)]);
+        let result = find_max_weight_clique_exact(&graph);
+        assert_eq!(result.nodes, vec![a]);
+        assert_eq!(result.weight, 2.5);
+    }
+
+    #[test]
+    fn test_unit_weights_match_cardinality() {
+        let graph = read_dimacs("data/brock200_2.clq")
+            .unwrap()
+            .map(|_, _| 1u32, |_, _| ());
+        let exact = find_max_weight_clique_exact(&graph);
+        assert_eq!(exact.weight, 12);
+        assert_eq!(exact.nodes.len(), 12);
+
+        let ga = find_max_weight_clique_with_ga(&graph);
+        assert!(ga.weight <= exact.weight);
+        assert_eq!(ga.weight as usize, ga.nodes.len());
+        for (i, &u) in ga.nodes.iter().enumerate() {
+            for &v in &ga.nodes[i + 1..] {
+                assert!(graph.contains_edge(u, v));
+            }
+        }
+    }
+}